@@ -0,0 +1,321 @@
+//! BLEU (BiLingual Evaluation Understudy) score, following Papineni et al. (2002).
+//! for now, sentence-level and corpus-level BLEU are provided. The texts are split on whitespace by default,
+//! where the results are the same as sacrebleu with `tokenize="none"`, and any `Tokenizer` can be used instead.
+//! The smoothing methods are from Chen and Cherry (2014).
+//!
+use std::collections::HashMap;
+use std::cmp::{max, min};
+use crate::commons::{MetricError, Result};
+use crate::rouge::create_ngrams;
+use crate::tokenizer::{as_strs, Tokenizer, WhitespaceTokenizer};
+
+/// The maximum n-gram order used by the standard BLEU.
+pub const DEFAULT_MAX_ORDER: usize = 4;
+
+/// Specifies how zero n-gram matches are smoothed, following Chen and Cherry (2014).
+///
+/// Without smoothing, BLEU is 0 as soon as one n-gram order has no match,
+/// which is the case for most short sentences.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Smoothing {
+    /// No smoothing, sacrebleu `smooth_method="none"`.
+    None,
+    /// Method 1: a zero match count is replaced by epsilon, 0.1 is commonly used.
+    /// The epsilon has to be finite and non-negative.
+    AddEpsilon(f32),
+    /// Method 2 (Lin and Och, 2004): 1 is added to the match count and the total count of orders 2 and above,
+    /// sacrebleu `smooth_method="add-k"` with `smooth_value=1`.
+    AddOne,
+    /// Method 3, NIST geometric sequence smoothing: the k-th order without match gets a precision of `1 / (2^k * total)`,
+    /// as in mteval-v13a. This is sacrebleu's default `smooth_method="exp"`.
+    NistGeometric,
+    /// Method 4: like `NistGeometric`, but the decay depends on the input length,
+    /// the k-th order without match gets `1 / (invcnt * total)` where `invcnt` is multiplied by `5 / ln(input_len)` each time.
+    /// This gives shorter inputs a higher smoothed precision.
+    LengthDependent,
+    /// An alias of `AddEpsilon` named after sacrebleu `smooth_method="floor"` (default value 0.1), which floors a zero
+    /// precision to `value / total`. It exists only for the sacrebleu naming, the scores are the same as `AddEpsilon`.
+    Floor(f32),
+}
+
+/// Represents a BLEU score together with the statistics it is computed from.
+///
+/// Unlike sacrebleu, the score is in `[0, 1]` and not multiplied by 100.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BleuScore {
+    /// The BLEU score.
+    pub score: f32,
+    /// The modified n-gram precisions, from unigrams to `max_order`-grams.
+    pub precisions: Vec<f32>,
+    /// The brevity penalty.
+    pub brevity_penalty: f32,
+    /// The total number of tokens in the inputs.
+    pub input_len: usize,
+    /// The total length of the references closest in length to each input.
+    pub reference_len: usize,
+}
+
+/// Counts the n-grams of each order from 1 to `max_order`, all in one `HashMap`.
+fn count_ngrams<'a>(tokens: &[&'a str], max_order: usize) -> HashMap<Vec<&'a str>, u32> {
+    let mut ngrams: HashMap<Vec<&str>, u32> = HashMap::new();
+    for n in 1..=max_order {
+        ngrams.extend(create_ngrams(tokens.to_vec(), n));
+    }
+    ngrams
+}
+
+/// Computes the modified n-gram precisions from the match counts and the total counts, applying `smoothing`.
+///
+/// Returns the precisions and the effective order, i.e. the number of orders with n-grams in the input.
+fn smoothed_precisions(matches: &[u32], totals: &[u32], input_len: usize, smoothing: Smoothing) -> (Vec<f32>, usize) {
+    let mut precisions: Vec<f32> = vec![0.0; matches.len()];
+    let mut effective_order: usize = 0;
+    let mut invcnt: f32 = 1.0;
+
+    for (n, precision) in precisions.iter_mut().enumerate() {
+        let (mut matched, mut total) = (matches[n] as f32, totals[n] as f32);
+        if smoothing == Smoothing::AddOne && n > 0 {
+            matched += 1.0;
+            total += 1.0;
+        }
+        // no n-gram of this order in the input, nothing to smooth
+        if total == 0.0 {
+            break;
+        }
+        effective_order = n + 1;
+
+        *precision = if matched > 0.0 {
+            matched / total
+        } else {
+            match smoothing {
+                Smoothing::AddEpsilon(epsilon) | Smoothing::Floor(epsilon) => epsilon / total,
+                Smoothing::NistGeometric => {
+                    invcnt *= 2.0;
+                    1.0 / (invcnt * total)
+                }
+                Smoothing::LengthDependent if input_len > 1 => {
+                    invcnt *= 5.0 / (input_len as f32).ln();
+                    1.0 / (invcnt * total)
+                }
+                _ => 0.0,
+            }
+        };
+    }
+    (precisions, effective_order)
+}
+
+/// Finds the reference length closest to the input length, preferring the shorter one on ties.
+fn closest_reference_len(input_len: usize, reference_lens: &[usize]) -> usize {
+    let mut closest = reference_lens[0];
+    for &reference_len in reference_lens.iter() {
+        let diff = input_len.abs_diff(reference_len);
+        let closest_diff = input_len.abs_diff(closest);
+        if diff < closest_diff || (diff == closest_diff && reference_len < closest) {
+            closest = reference_len;
+        }
+    }
+    closest
+}
+
+/// Computes the brevity penalty, which punishes inputs shorter than their references.
+///
+/// ### Arguments
+///
+/// * `input_len` - The total number of tokens in the inputs.
+/// * `reference_len` - The total length of the references closest in length to each input.
+///
+/// ### Returns
+///
+/// `1` if the inputs are not shorter than the references, `exp(1 - reference_len / input_len)` otherwise.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::bleu::brevity_penalty;
+///
+/// assert_eq!(1.0, brevity_penalty(12, 10));
+/// assert_eq!(0.0, brevity_penalty(0, 10));
+/// ```
+pub fn brevity_penalty(input_len: usize, reference_len: usize) -> f32 {
+    if input_len >= reference_len {
+        1.0
+    } else if input_len == 0 {
+        0.0
+    } else {
+        (1.0 - reference_len as f32 / input_len as f32).exp()
+    }
+}
+
+/// Computes corpus-level BLEU scores for the given inputs and their references.
+///
+/// The clipped n-gram matches and the n-gram counts are summed over the whole corpus
+/// before the modified precisions are computed, so this is not the average of sentence-level scores.
+///
+/// ### Arguments
+///
+/// * `inputs` - The input texts to be evaluated.
+/// * `references` - One or more reference texts for each input, in the same order as `inputs`.
+/// * `max_order` - The maximum n-gram order, `DEFAULT_MAX_ORDER` (4) for the standard BLEU.
+/// * `smoothing` - How to smooth n-gram orders without match, `Smoothing::NistGeometric` is the sacrebleu default.
+///
+/// ### Returns
+///
+/// A `Result` containing a `BleuScore` if successful, or an error message if `max_order` is less than 1,
+/// the smoothing value is negative or not finite, the number of inputs and references differ, or an input has no reference.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::bleu::{corpus_bleu, Smoothing, DEFAULT_MAX_ORDER};
+///
+/// let inputs = ["the cat is on the mat", "there is a dog in the garden"];
+/// let references = [vec!["the cat is on the mat"], vec!["a dog is in the garden", "there is a dog in a garden"]];
+///
+/// let score = corpus_bleu(&inputs, &references, DEFAULT_MAX_ORDER, Smoothing::NistGeometric).unwrap();
+/// println!("BLEU: {}", score.score);
+/// println!("Precisions: {:?}", score.precisions);
+/// ```
+///
+/// # Note
+///
+/// - The texts are split into words on whitespace, see `corpus_bleu_with_tokenizer` for other tokenizers.
+/// - Each input n-gram count is clipped by its maximum count in any one of the references.
+/// - For each input, the reference length closest to the input length is used for the brevity penalty.
+/// - If any n-gram order has no match after smoothing, the score is 0.
+/// - As in sacrebleu (`effective_order=False`), the precisions of all `max_order` orders are averaged, so the score
+///   is 0 if no input has `max_order` tokens, even with smoothing. `sentence_bleu` uses the effective order instead.
+pub fn corpus_bleu(inputs: &[&str], references: &[Vec<&str>], max_order: usize, smoothing: Smoothing) -> Result<BleuScore> {
+    corpus_bleu_with_tokenizer(inputs, references, max_order, smoothing, &WhitespaceTokenizer)
+}
+
+/// Computes corpus-level BLEU scores, splitting the texts into tokens with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `inputs` - The input texts to be evaluated.
+/// * `references` - One or more reference texts for each input, in the same order as `inputs`.
+/// * `max_order` - The maximum n-gram order, `DEFAULT_MAX_ORDER` (4) for the standard BLEU.
+/// * `smoothing` - How to smooth n-gram orders without match, `Smoothing::NistGeometric` is the sacrebleu default.
+/// * `tokenizer` - The tokenizer splitting every text into tokens.
+///
+/// ### Returns
+///
+/// A `Result` containing a `BleuScore` if successful, see `corpus_bleu`.
+pub fn corpus_bleu_with_tokenizer(inputs: &[&str], references: &[Vec<&str>], max_order: usize, smoothing: Smoothing, tokenizer: &dyn Tokenizer) -> Result<BleuScore> {
+    bleu(inputs, references, max_order, smoothing, tokenizer, false)
+}
+
+/// Computes BLEU, averaging only over the orders with n-grams in the inputs if `effective_order` is set.
+fn bleu(inputs: &[&str], references: &[Vec<&str>], max_order: usize, smoothing: Smoothing, tokenizer: &dyn Tokenizer, effective_order: bool) -> Result<BleuScore> {
+    if max_order < 1 {
+        return Err(MetricError::InvalidN(max_order));
+    }
+    if let Smoothing::AddEpsilon(epsilon) | Smoothing::Floor(epsilon) = smoothing {
+        if !epsilon.is_finite() || epsilon < 0.0 {
+            return Err(MetricError::InvalidWeight(epsilon));
+        }
+    }
+    if inputs.len() != references.len() {
+        return Err(MetricError::LengthMismatch{inputs: inputs.len(), references: references.len()});
+    }
+    if let Some(i) = references.iter().position(|r| r.is_empty()) {
+        return Err(MetricError::MissingReference(i));
+    }
+
+    let mut matches: Vec<u32> = vec![0; max_order];
+    let mut totals: Vec<u32> = vec![0; max_order];
+    let mut input_len: usize = 0;
+    let mut reference_len: usize = 0;
+
+    for (input, input_references) in inputs.iter().zip(references.iter()) {
+        let input_tokens = tokenizer.tokenize(input);
+        let reference_tokens: Vec<Vec<String>> = input_references.iter().map(|r| tokenizer.tokenize(r)).collect();
+        let input_words: Vec<&str> = as_strs(&input_tokens);
+        let reference_words: Vec<Vec<&str>> = reference_tokens.iter().map(|r| as_strs(r)).collect();
+
+        // maximum count of each n-gram in any one of the references
+        let mut max_reference_ngrams: HashMap<Vec<&str>, u32> = HashMap::new();
+        for words in reference_words.iter() {
+            for (ngram, cnt) in count_ngrams(words, max_order) {
+                let max_cnt = max_reference_ngrams.entry(ngram).or_insert(0);
+                *max_cnt = max(*max_cnt, cnt);
+            }
+        }
+
+        for (ngram, cnt) in count_ngrams(&input_words, max_order) {
+            matches[ngram.len() - 1] += min(cnt, *max_reference_ngrams.get(&ngram).unwrap_or(&0));
+        }
+        for (n, total) in totals.iter_mut().enumerate() {
+            *total += input_words.len().saturating_sub(n) as u32;
+        }
+
+        let reference_lens: Vec<usize> = reference_words.iter().map(|r| r.len()).collect();
+        reference_len += closest_reference_len(input_words.len(), &reference_lens);
+        input_len += input_words.len();
+    }
+
+    let (precisions, order) = smoothed_precisions(&matches, &totals, input_len, smoothing);
+    let bp = brevity_penalty(input_len, reference_len);
+
+    let order = if effective_order { order } else { max_order };
+    let score = if order == 0 || precisions[..order].contains(&0.0) {
+        0.0
+    } else {
+        bp * (precisions[..order].iter().map(|p| p.ln()).sum::<f32>() / order as f32).exp()
+    };
+
+    Ok(BleuScore{score, precisions, brevity_penalty: bp, input_len, reference_len})
+}
+
+/// Computes sentence-level BLEU scores for a given input and its references.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `references` - One or more reference texts.
+/// * `max_order` - The maximum n-gram order, `DEFAULT_MAX_ORDER` (4) for the standard BLEU.
+/// * `smoothing` - How to smooth n-gram orders without match, which matters a lot for short sentences.
+///
+/// ### Returns
+///
+/// A `Result` containing a `BleuScore` if successful, or an error message if `max_order` is less than 1,
+/// the smoothing value is negative or not finite, or `references` is empty.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::bleu::{sentence_bleu, Smoothing, DEFAULT_MAX_ORDER};
+///
+/// let score = sentence_bleu("the cat is on the mat", &["the cat is on the mat"], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+/// assert_eq!(1.0, score.score);
+///
+/// // no 4-gram match, but smoothing keeps the score above 0
+/// let score = sentence_bleu("this is a fest", &["this is a test"], DEFAULT_MAX_ORDER, Smoothing::AddOne).unwrap();
+/// assert!(score.score > 0.0);
+/// ```
+///
+/// # Note
+///
+/// - As in sacrebleu (`effective_order=True`), only the orders with n-grams in the input are averaged,
+///   even without smoothing, so an input shorter than `max_order` tokens can still score above 0.
+///   Otherwise this is the same as `corpus_bleu` with a single input.
+pub fn sentence_bleu(input: &str, references: &[&str], max_order: usize, smoothing: Smoothing) -> Result<BleuScore> {
+    sentence_bleu_with_tokenizer(input, references, max_order, smoothing, &WhitespaceTokenizer)
+}
+
+/// Computes sentence-level BLEU scores, splitting the texts into tokens with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `references` - One or more reference texts.
+/// * `max_order` - The maximum n-gram order, `DEFAULT_MAX_ORDER` (4) for the standard BLEU.
+/// * `smoothing` - How to smooth n-gram orders without match.
+/// * `tokenizer` - The tokenizer splitting every text into tokens.
+///
+/// ### Returns
+///
+/// A `Result` containing a `BleuScore` if successful, see `sentence_bleu`.
+pub fn sentence_bleu_with_tokenizer(input: &str, references: &[&str], max_order: usize, smoothing: Smoothing, tokenizer: &dyn Tokenizer) -> Result<BleuScore> {
+    bleu(&[input], &[references.to_vec()], max_order, smoothing, tokenizer, true)
+}
@@ -0,0 +1,293 @@
+//! chrF and chrF++ scores, following Popović (2015, 2017).
+//! The results are the same as sacrebleu's `CHRF` with its defaults,
+//! i.e. whitespace is not part of character n-grams and orders without any n-gram are left out of the averages.
+//!
+use std::collections::HashMap;
+use std::cmp::min;
+use crate::commons::{f_beta, MetricError, Result, Score};
+use crate::rouge::create_ngrams;
+use crate::tokenizer::{as_strs, Tokenizer};
+
+/// The maximum character n-gram order of chrF.
+pub const DEFAULT_CHAR_ORDER: usize = 6;
+/// The maximum word n-gram order of chrF++.
+pub const CHRF_PLUS_PLUS_WORD_ORDER: usize = 2;
+/// The default beta of chrF, recall is twice as important as precision.
+pub const DEFAULT_BETA: f32 = 2.0;
+
+/// The punctuation split off from words for the word n-grams of chrF++, same as python's `string.punctuation`.
+const PUNCTUATION: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// Configuration of chrF.
+///
+/// The default is chrF as in sacrebleu, and `ChrfConfig::chrf_plus_plus()` adds word bigrams for chrF++.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::chrf::ChrfConfig;
+///
+/// let chrf = ChrfConfig::default();
+/// let chrf_plus_plus = ChrfConfig::chrf_plus_plus();
+/// let chrf_with_whitespace = ChrfConfig{include_whitespace: true, ..ChrfConfig::default()};
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChrfConfig {
+    /// The maximum character n-gram order.
+    pub char_order: usize,
+    /// The maximum word n-gram order, 0 for chrF and 2 for chrF++.
+    pub word_order: usize,
+    /// The weight of recall relative to precision, which has to be finite.
+    pub beta: f32,
+    /// Whether whitespace is part of character n-grams.
+    pub include_whitespace: bool,
+}
+
+impl Default for ChrfConfig {
+    fn default() -> Self {
+        ChrfConfig{char_order: DEFAULT_CHAR_ORDER, word_order: 0, beta: DEFAULT_BETA, include_whitespace: false}
+    }
+}
+
+impl ChrfConfig {
+    /// Creates the configuration of chrF++, which also counts word unigrams and bigrams.
+    pub fn chrf_plus_plus() -> Self {
+        ChrfConfig{word_order: CHRF_PLUS_PLUS_WORD_ORDER, ..ChrfConfig::default()}
+    }
+}
+
+/// Splits a text into characters, skipping whitespace unless `include_whitespace` is set.
+fn split_chars(text: &str, include_whitespace: bool) -> Vec<&str> {
+    text.char_indices()
+        .filter(|(_, c)| include_whitespace || !c.is_whitespace())
+        .map(|(i, c)| &text[i..i + c.len_utf8()])
+        .collect()
+}
+
+/// Splits a text into words, separating a punctuation mark at the end (or else the start) of each word, as sacrebleu does.
+fn split_words(text: &str) -> Vec<&str> {
+    let mut words: Vec<&str> = Vec::new();
+    for word in text.split_whitespace() {
+        let first = word.chars().next().unwrap();
+        let last = word.chars().next_back().unwrap();
+        if word.chars().count() == 1 {
+            words.push(word);
+        } else if PUNCTUATION.contains(last) {
+            words.extend([&word[..word.len() - last.len_utf8()], &word[word.len() - last.len_utf8()..]]);
+        } else if PUNCTUATION.contains(first) {
+            words.extend([&word[..first.len_utf8()], &word[first.len_utf8()..]]);
+        } else {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Splits a text into the words of chrF++ as sacrebleu does, see `split_words`.
+struct ChrfWordTokenizer;
+
+impl Tokenizer for ChrfWordTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        split_words(text).into_iter().map(str::to_string).collect()
+    }
+}
+
+/// Counts the n-grams of each order from 1 to `max_order`, one `HashMap` per order.
+fn count_ngrams<'a>(tokens: &[&'a str], max_order: usize) -> Vec<HashMap<Vec<&'a str>, u32>> {
+    (1..=max_order)
+        .map(|n| create_ngrams(tokens.to_vec(), n))
+        .collect()
+}
+
+/// Counts the character n-grams of a text followed by the n-grams of its words.
+fn extract_ngrams<'a>(text: &'a str, words: &[&'a str], config: &ChrfConfig) -> Vec<HashMap<Vec<&'a str>, u32>> {
+    let mut ngrams = count_ngrams(&split_chars(text, config.include_whitespace), config.char_order);
+    ngrams.extend(count_ngrams(words, config.word_order));
+    ngrams
+}
+
+/// Computes `[input n-grams, reference n-grams, matched n-grams]` for each order.
+fn match_statistics(input_ngrams: &[HashMap<Vec<&str>, u32>], reference_ngrams: &[HashMap<Vec<&str>, u32>]) -> Vec<[u32; 3]> {
+    input_ngrams
+        .iter()
+        .zip(reference_ngrams.iter())
+        .map(|(input, reference)| {
+            let matched: u32 = input
+                .iter()
+                .map(|(ngram, cnt)| min(*cnt, *reference.get(ngram).unwrap_or(&0)))
+                .sum();
+            [input.values().sum(), reference.values().sum(), matched]
+        })
+        .collect()
+}
+
+/// Computes the chrF score from the match statistics of every order.
+///
+/// Precision and recall are averaged over the orders where both the input and the reference have n-grams,
+/// and the score is the F-beta of the averages.
+fn chrf_score(statistics: &[[u32; 3]], beta: f32) -> Score {
+    // sacrebleu uses a tiny epsilon in place of the precision and recall of orders without n-grams
+    let eps: f32 = 1e-16;
+    let mut effective_order: u32 = 0;
+    let (mut p, mut r): (f32, f32) = (0.0, 0.0);
+
+    for &[input_cnt, reference_cnt, matched] in statistics.iter() {
+        p += if input_cnt > 0 { matched as f32 / input_cnt as f32 } else { eps };
+        r += if reference_cnt > 0 { matched as f32 / reference_cnt as f32 } else { eps };
+        if input_cnt > 0 && reference_cnt > 0 {
+            effective_order += 1;
+        }
+    }
+
+    if effective_order == 0 {
+        return Score{precision: 0.0, recall: 0.0, f1: 0.0, beta};
+    }
+    p /= effective_order as f32;
+    r /= effective_order as f32;
+
+    Score{precision: p, recall: r, f1: f_beta(p, r, beta), beta}
+}
+
+/// Computes corpus-level chrF scores for the given inputs and their references.
+///
+/// chrF is the F-beta score of character n-gram precision and recall, averaged over the n-gram orders.
+/// chrF++ additionally counts word n-grams, which correlates better with human judgements.
+///
+/// ### Arguments
+///
+/// * `inputs` - The input texts to be evaluated.
+/// * `references` - One or more reference texts for each input, in the same order as `inputs`.
+/// * `config` - The n-gram orders, beta and whitespace handling, see `ChrfConfig`.
+///
+/// ### Returns
+///
+/// A `Result` containing a `Score` struct if successful, or an error message if `config.char_order` is less than 1,
+/// `config.beta` is not finite, the number of inputs and references differ, or an input has no reference.
+/// The F-beta score is stored in the `f1` field.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::chrf::{corpus_chrf, ChrfConfig};
+///
+/// let inputs = ["Die Beziehung zwischen Obama und Netanjahu ist nicht gerade freundlich."];
+/// let references = [vec!["Das Verhältnis zwischen Obama und Netanyahu ist nicht gerade freundschaftlich."]];
+///
+/// let chrf = corpus_chrf(&inputs, &references, &ChrfConfig::default()).unwrap();
+/// let chrf_plus_plus = corpus_chrf(&inputs, &references, &ChrfConfig::chrf_plus_plus()).unwrap();
+/// println!("chrF: {}, chrF++: {}", chrf.f1, chrf_plus_plus.f1);
+/// ```
+///
+/// # Note
+///
+/// - With multiple references, the reference with the best score is used for each input.
+/// - The match statistics are summed over the corpus before the score is computed.
+/// - Word n-grams are created from whitespace separated words, with a punctuation mark at the end
+///   or the start of a word split off.
+pub fn corpus_chrf(inputs: &[&str], references: &[Vec<&str>], config: &ChrfConfig) -> Result<Score> {
+    corpus_chrf_with_tokenizer(inputs, references, config, &ChrfWordTokenizer)
+}
+
+/// Computes corpus-level chrF scores, splitting the texts into the words of chrF++ with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `inputs` - The input texts to be evaluated.
+/// * `references` - One or more reference texts for each input, in the same order as `inputs`.
+/// * `config` - The n-gram orders, beta and whitespace handling, see `ChrfConfig`.
+/// * `tokenizer` - The tokenizer splitting every text into words.
+///
+/// ### Returns
+///
+/// A `Result` containing a `Score` struct if successful, see `corpus_chrf`.
+///
+/// # Note
+///
+/// - The tokenizer only affects word n-grams, so the score is the same as `corpus_chrf` if `config.word_order` is 0.
+///   In particular, a `NormalizingTokenizer` does not normalize the character n-grams, see `Normalizer::score` instead.
+pub fn corpus_chrf_with_tokenizer(inputs: &[&str], references: &[Vec<&str>], config: &ChrfConfig, tokenizer: &dyn Tokenizer) -> Result<Score> {
+    if config.char_order < 1 {
+        return Err(MetricError::InvalidN(config.char_order));
+    }
+    if !config.beta.is_finite() {
+        return Err(MetricError::InvalidWeight(config.beta));
+    }
+    if inputs.len() != references.len() {
+        return Err(MetricError::LengthMismatch{inputs: inputs.len(), references: references.len()});
+    }
+    if let Some(i) = references.iter().position(|r| r.is_empty()) {
+        return Err(MetricError::MissingReference(i));
+    }
+
+    let mut statistics: Vec<[u32; 3]> = vec![[0; 3]; config.char_order + config.word_order];
+    for (input, input_references) in inputs.iter().zip(references.iter()) {
+        let input_words = tokenizer.tokenize(input);
+        let input_ngrams = extract_ngrams(input, &as_strs(&input_words), config);
+
+        // statistics against the best reference
+        let mut best_f: f32 = -1.0;
+        let mut best_statistics: Vec<[u32; 3]> = Vec::new();
+        for reference in input_references.iter() {
+            let reference_words = tokenizer.tokenize(reference);
+            let reference_statistics = match_statistics(&input_ngrams, &extract_ngrams(reference, &as_strs(&reference_words), config));
+            let f = chrf_score(&reference_statistics, config.beta).f1;
+            if f > best_f {
+                best_f = f;
+                best_statistics = reference_statistics;
+            }
+        }
+
+        for (total, best_statistics) in statistics.iter_mut().zip(best_statistics) {
+            for k in 0..3 {
+                total[k] += best_statistics[k];
+            }
+        }
+    }
+
+    Ok(chrf_score(&statistics, config.beta))
+}
+
+/// Computes sentence-level chrF scores for a given input and its references.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `references` - One or more reference texts.
+/// * `config` - The n-gram orders, beta and whitespace handling, see `ChrfConfig`.
+///
+/// ### Returns
+///
+/// A `Result` containing a `Score` struct if successful, or an error message if `config.char_order` is less than 1,
+/// `config.beta` is not finite, or `references` is empty. The F-beta score is stored in the `f1` field.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::chrf::{sentence_chrf, ChrfConfig};
+///
+/// let score = sentence_chrf("a b c", &["abc"], &ChrfConfig::default()).unwrap();
+/// assert_eq!(1.0, score.f1);
+/// ```
+///
+/// # Note
+///
+/// - This is the same as `corpus_chrf` with a single input.
+pub fn sentence_chrf(input: &str, references: &[&str], config: &ChrfConfig) -> Result<Score> {
+    corpus_chrf(&[input], &[references.to_vec()], config)
+}
+
+/// Computes sentence-level chrF scores, splitting the texts into the words of chrF++ with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `references` - One or more reference texts.
+/// * `config` - The n-gram orders, beta and whitespace handling, see `ChrfConfig`.
+/// * `tokenizer` - The tokenizer splitting every text into words.
+///
+/// ### Returns
+///
+/// A `Result` containing a `Score` struct if successful, see `sentence_chrf`.
+pub fn sentence_chrf_with_tokenizer(input: &str, references: &[&str], config: &ChrfConfig, tokenizer: &dyn Tokenizer) -> Result<Score> {
+    corpus_chrf_with_tokenizer(&[input], &[references.to_vec()], config, tokenizer)
+}
@@ -0,0 +1,495 @@
+//! Metrics of classification, built on a confusion matrix counting each pair of true and predicted labels.
+//!
+//! Labels can be of any hashable type, e.g. `&str`, `String`, integers or enums.
+//! Multi-label classification, where each sample has a set of labels, is scored with `multilabel_report`.
+//!
+use std::cmp::max;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+use crate::commons::{f_beta, precision, recall, MetricError, Result, Score, ZeroDivision};
+
+/// The true positives, false positives, false negatives and true negatives of one class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ClassCounts {
+    /// The number of samples of the class predicted as the class.
+    pub true_pos: u32,
+    /// The number of samples of other classes predicted as the class.
+    pub false_pos: u32,
+    /// The number of samples of the class predicted as another class.
+    pub false_neg: u32,
+    /// The number of samples of other classes predicted as another class.
+    pub true_neg: u32,
+}
+
+impl ClassCounts {
+    /// The number of samples whose true label is the class, `true_pos + false_neg`.
+    pub fn support(&self) -> u32 {
+        self.true_pos + self.false_neg
+    }
+}
+
+/// Specifies how the counts of a confusion matrix are normalized, as `normalize` of scikit-learn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// Divides each row by the number of samples with that true label, giving the recall on the diagonal.
+    Rows,
+    /// Divides each column by the number of samples with that predicted label, giving the precision on the diagonal.
+    Columns,
+    /// Divides every count by the total number of samples.
+    All,
+}
+
+/// A confusion matrix of multi-class classification, where rows are true labels and columns are predicted labels.
+///
+/// The labels are ordered by first appearance, unless given in advance with `ConfusionMatrix::with_labels`.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::classification::ConfusionMatrix;
+///
+/// let mut matrix = ConfusionMatrix::from_labels(&["cat", "dog", "cat"], &["cat", "cat", "cat"]).unwrap();
+/// matrix.add("dog", "dog");
+///
+/// let cat = matrix.class_counts(&"cat");
+/// assert_eq!((2, 1, 0, 1), (cat.true_pos, cat.false_pos, cat.false_neg, cat.true_neg));
+/// println!("{}", matrix);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfusionMatrix<L: Eq + Hash + Clone> {
+    labels: Vec<L>,
+    index: HashMap<L, usize>,
+    counts: Vec<Vec<u32>>,
+}
+
+impl<L: Eq + Hash + Clone> Default for ConfusionMatrix<L> {
+    fn default() -> Self {
+        ConfusionMatrix{labels: Vec::new(), index: HashMap::new(), counts: Vec::new()}
+    }
+}
+
+impl<L: Eq + Hash + Clone> ConfusionMatrix<L> {
+    /// Creates an empty matrix.
+    pub fn new() -> Self {
+        ConfusionMatrix::default()
+    }
+
+    /// Creates an empty matrix with the given labels, in that order. Other labels are appended when they appear.
+    pub fn with_labels(labels: &[L]) -> Self {
+        let mut matrix = ConfusionMatrix::new();
+        for label in labels.iter() {
+            matrix.position(label);
+        }
+        matrix
+    }
+
+    /// Creates a matrix from the true and predicted labels of samples.
+    ///
+    /// ### Arguments
+    ///
+    /// * `y_true` - The true label of each sample.
+    /// * `y_pred` - The predicted label of each sample.
+    ///
+    /// ### Returns
+    ///
+    /// A `Result` containing the matrix if successful, or `MetricError::LengthMismatch` if the lengths differ.
+    pub fn from_labels(y_true: &[L], y_pred: &[L]) -> Result<Self> {
+        let mut matrix = ConfusionMatrix::new();
+        matrix.update(y_true, y_pred)?;
+        Ok(matrix)
+    }
+
+    /// Adds the true and predicted labels of more samples, see `ConfusionMatrix::from_labels`.
+    /// Nothing is added if the lengths differ.
+    pub fn update(&mut self, y_true: &[L], y_pred: &[L]) -> Result<()> {
+        if y_true.len() != y_pred.len() {
+            return Err(MetricError::LengthMismatch{inputs: y_pred.len(), references: y_true.len()});
+        }
+        for (true_label, predicted) in y_true.iter().zip(y_pred.iter()) {
+            self.add(true_label.clone(), predicted.clone());
+        }
+        Ok(())
+    }
+
+    /// Adds one sample.
+    pub fn add(&mut self, true_label: L, predicted: L) {
+        let i = self.position(&true_label);
+        let j = self.position(&predicted);
+        self.counts[i][j] += 1;
+    }
+
+    /// Returns the index of a label, adding a row and a column if it is new.
+    fn position(&mut self, label: &L) -> usize {
+        if let Some(&i) = self.index.get(label) {
+            return i;
+        }
+        let i = self.labels.len();
+        self.labels.push(label.clone());
+        self.index.insert(label.clone(), i);
+        for row in self.counts.iter_mut() {
+            row.push(0);
+        }
+        self.counts.push(vec![0; i + 1]);
+        i
+    }
+
+    /// Returns the labels, in the order of the rows and columns.
+    pub fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    /// Returns the counts, `counts()[i][j]` being the number of samples of label `i` predicted as label `j`.
+    pub fn counts(&self) -> &[Vec<u32>] {
+        &self.counts
+    }
+
+    /// Returns the number of samples with the given true and predicted labels.
+    pub fn count(&self, true_label: &L, predicted: &L) -> u32 {
+        match (self.index.get(true_label), self.index.get(predicted)) {
+            (Some(&i), Some(&j)) => self.counts[i][j],
+            _ => 0,
+        }
+    }
+
+    /// Returns the number of samples.
+    pub fn total(&self) -> u32 {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// Returns the counts of one class against all the others. A label never seen has only true negatives.
+    pub fn class_counts(&self, label: &L) -> ClassCounts {
+        let total = self.total();
+        let Some(&i) = self.index.get(label) else {
+            return ClassCounts{true_neg: total, ..ClassCounts::default()};
+        };
+        let true_pos = self.counts[i][i];
+        let false_neg = self.counts[i].iter().sum::<u32>() - true_pos;
+        let false_pos = self.counts.iter().map(|row| row[i]).sum::<u32>() - true_pos;
+        ClassCounts{true_pos, false_pos, false_neg, true_neg: total - true_pos - false_pos - false_neg}
+    }
+
+    /// Computes the precision, recall and F1 score of every class, and their averages.
+    ///
+    /// ### Arguments
+    ///
+    /// * `zero_division` - The policy for the precision of a class never predicted, the recall of a class never
+    ///   true, and the F1 score of a class neither predicted nor true.
+    ///
+    /// ### Returns
+    ///
+    /// A `Result` containing the report if successful, or `MetricError::ZeroDivision` if the policy is
+    /// `ZeroDivision::Error` and a division by zero occurs.
+    pub fn report(&self, zero_division: ZeroDivision) -> Result<ClassificationReport<L>> {
+        let mut classes: Vec<ClassReport<L>> = Vec::new();
+        let mut total = ClassCounts::default();
+        for label in self.labels.iter() {
+            let counts = self.class_counts(label);
+            classes.push(ClassReport{label: label.clone(), score: counts_score(counts, zero_division)?, support: counts.support()});
+            total.true_pos += counts.true_pos;
+            total.false_pos += counts.false_pos;
+            total.false_neg += counts.false_neg;
+        }
+        let support = self.total();
+
+        let scores: Vec<Score> = classes.iter().map(|c| c.score).collect();
+        let macro_avg = weighted_mean(&scores, &vec![1.0; scores.len()], zero_division)?;
+        let weighted_avg = weighted_mean(&scores, &classes.iter().map(|c| c.support as f32).collect::<Vec<f32>>(), zero_division)?;
+        let micro_avg = counts_score(total, zero_division)?;
+        let accuracy = zero_division.divide(total.true_pos as f32, support as f32)?;
+
+        Ok(ClassificationReport{classes, accuracy, micro_avg, macro_avg, weighted_avg, support})
+    }
+
+    /// Returns the counts divided by the sums of their rows, their columns or all counts.
+    /// Rows or columns without any sample are all 0.
+    pub fn normalize(&self, normalization: Normalization) -> Vec<Vec<f32>> {
+        let n = self.labels.len();
+        let row_sums: Vec<u32> = self.counts.iter().map(|row| row.iter().sum()).collect();
+        let column_sums: Vec<u32> = (0..n).map(|j| self.counts.iter().map(|row| row[j]).sum()).collect();
+        let total = self.total();
+
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        let sum = match normalization {
+                            Normalization::Rows => row_sums[i],
+                            Normalization::Columns => column_sums[j],
+                            Normalization::All => total,
+                        };
+                        if sum == 0 { 0.0 } else { self.counts[i][j] as f32 / sum as f32 }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Computes precision, recall and F1 score from the counts of a class, with the F1 score computed from the counts as
+/// scikit-learn does, so that `zero_division` only applies to it if there are no true positives, false positives
+/// and false negatives.
+pub(crate) fn counts_score(counts: ClassCounts, zero_division: ZeroDivision) -> Result<Score> {
+    let ClassCounts{true_pos, false_pos, false_neg, ..} = counts;
+    if true_pos + false_pos + false_neg == 0 {
+        let value = zero_division.divide(0.0, 0.0)?;
+        return Ok(Score{precision: value, recall: value, f1: value, beta: 1.0});
+    }
+    let p = if true_pos + false_pos > 0 { precision(true_pos, false_pos) } else { zero_division.divide(0.0, 0.0)? };
+    let r = if true_pos + false_neg > 0 { recall(true_pos, false_neg) } else { zero_division.divide(0.0, 0.0)? };
+    let f = if true_pos > 0 { f_beta(p, r, 1.0) } else { 0.0 };
+    Ok(Score{precision: p, recall: r, f1: f, beta: 1.0})
+}
+
+/// Computes the field-wise mean of scores weighted by `weights`, applying `zero_division` if the weights sum to 0.
+pub(crate) fn weighted_mean(scores: &[Score], weights: &[f32], zero_division: ZeroDivision) -> Result<Score> {
+    let total: f32 = weights.iter().sum();
+    let sum = |field: fn(&Score) -> f32| scores.iter().zip(weights.iter()).map(|(s, w)| w * field(s)).sum::<f32>();
+    Ok(Score{
+        precision: zero_division.divide(sum(|s| s.precision), total)?,
+        recall: zero_division.divide(sum(|s| s.recall), total)?,
+        f1: zero_division.divide(sum(|s| s.f1), total)?,
+        beta: 1.0,
+    })
+}
+
+impl<L: Eq + Hash + Clone + fmt::Display> fmt::Display for ConfusionMatrix<L> {
+    /// Formats the matrix as a table, with a header of predicted labels and a row per true label.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = self.labels.iter().map(|label| label.to_string()).collect();
+        let corner = "true\\pred";
+        let label_width = names.iter().map(|name| name.chars().count()).chain([corner.len()]).max().unwrap_or(0);
+        let width = names
+            .iter()
+            .map(|name| name.chars().count())
+            .chain(self.counts.iter().flatten().map(|count| count.to_string().len()))
+            .max()
+            .unwrap_or(0);
+
+        write!(f, "{:>label_width$}", corner)?;
+        for name in names.iter() {
+            write!(f, " {:>width$}", name)?;
+        }
+        for (name, row) in names.iter().zip(self.counts.iter()) {
+            write!(f, "\n{:>label_width$}", name)?;
+            for count in row.iter() {
+                write!(f, " {:>width$}", count)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The score and the support of one class in a `ClassificationReport`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassReport<L> {
+    pub label: L,
+    pub score: Score,
+    /// The number of samples whose true label is the class.
+    pub support: u32,
+}
+
+/// Represents the per-class scores of a multi-class classification and their averages,
+/// as `classification_report` of scikit-learn.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::classification::classification_report;
+///
+/// let report = classification_report(&[0, 1, 2, 2, 2], &[0, 0, 2, 2, 1]).unwrap();
+/// assert_eq!(0.6, report.accuracy);
+/// assert_eq!(0.5, report.macro_avg.precision);
+/// println!("{:.2}", report);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassificationReport<L> {
+    /// The score of each class, in the order of the labels of the confusion matrix.
+    pub classes: Vec<ClassReport<L>>,
+    /// The fraction of samples whose predicted label is the true label.
+    pub accuracy: f32,
+    /// The score of the true positives, false positives and false negatives summed over the classes.
+    /// For multi-class classification, precision, recall and F1 score are all equal to the accuracy.
+    pub micro_avg: Score,
+    /// The unweighted mean of the scores of the classes.
+    pub macro_avg: Score,
+    /// The mean of the scores of the classes weighted by their support.
+    pub weighted_avg: Score,
+    /// The number of samples.
+    pub support: u32,
+}
+
+impl<L: PartialEq> ClassificationReport<L> {
+    /// Returns the score and support of a class, if it appears in the samples.
+    pub fn class(&self, label: &L) -> Option<&ClassReport<L>> {
+        self.classes.iter().find(|c| c.label == *label)
+    }
+}
+
+impl<L: fmt::Display> fmt::Display for ClassificationReport<L> {
+    /// Formats the report as scikit-learn does, with the precision of the formatter as the number of digits (2 by default).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = f.precision().unwrap_or(2);
+        let names: Vec<String> = self.classes.iter().map(|c| c.label.to_string()).collect();
+        let width = names.iter().map(|name| name.chars().count()).chain(["weighted avg".len()]).max().unwrap_or(0);
+        let column = max(digits + 2, "precision".len());
+
+        writeln!(f, "{:>width$}  {:>column$} {:>column$} {:>column$} {:>column$}", "", "precision", "recall", "f1-score", "support")?;
+        for (name, class) in names.iter().zip(self.classes.iter()) {
+            let s = class.score;
+            write!(f, "\n{:>width$}  {:>column$.digits$} {:>column$.digits$} {:>column$.digits$} {:>column$}", name, s.precision, s.recall, s.f1, class.support)?;
+        }
+        write!(f, "\n\n{:>width$}  {:>column$} {:>column$} {:>column$.digits$} {:>column$}", "accuracy", "", "", self.accuracy, self.support)?;
+        for (name, s) in [("macro avg", self.macro_avg), ("weighted avg", self.weighted_avg)] {
+            write!(f, "\n{:>width$}  {:>column$.digits$} {:>column$.digits$} {:>column$.digits$} {:>column$}", name, s.precision, s.recall, s.f1, self.support)?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the classification report of true and predicted labels, see `ConfusionMatrix::report`.
+///
+/// ### Arguments
+///
+/// * `y_true` - The true label of each sample.
+/// * `y_pred` - The predicted label of each sample.
+///
+/// ### Returns
+///
+/// A `Result` containing the report if successful, or `MetricError::LengthMismatch` if the lengths differ.
+///
+/// # Note
+///
+/// - Scores which divide by zero are 0, see `ConfusionMatrix::report` for other policies.
+/// - Classes are in order of first appearance, use `ConfusionMatrix::with_labels` for another order,
+///   e.g. the sorted order of scikit-learn.
+pub fn classification_report<L: Eq + Hash + Clone>(y_true: &[L], y_pred: &[L]) -> Result<ClassificationReport<L>> {
+    ConfusionMatrix::from_labels(y_true, y_pred)?.report(ZeroDivision::Zero)
+}
+
+/// Represents the scores of a multi-label classification, as the multi-label metrics of scikit-learn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiLabelReport<L> {
+    /// The score of each label against all the samples, in order of first appearance. The labels first appearing
+    /// in the same sample are in no particular order.
+    pub labels: Vec<ClassReport<L>>,
+    /// The mean of the scores of each sample, computed from its true and predicted sets.
+    pub samples_avg: Score,
+    /// The score of the true positives, false positives and false negatives summed over the labels.
+    pub micro_avg: Score,
+    /// The unweighted mean of the scores of the labels.
+    pub macro_avg: Score,
+    /// The mean of the scores of the labels weighted by their support.
+    pub weighted_avg: Score,
+    /// The fraction of wrong (sample, label) pairs, i.e. labels missed or predicted wrongly, out of all pairs.
+    pub hamming_loss: f32,
+    /// The fraction of samples whose predicted set is exactly the true set.
+    pub subset_accuracy: f32,
+    /// The mean over the samples of the Jaccard index, `|true & predicted| / |true | predicted|`.
+    pub jaccard: f32,
+    /// The number of samples.
+    pub n_samples: u32,
+}
+
+impl<L: PartialEq> MultiLabelReport<L> {
+    /// Returns the score and support of a label, if it appears in the samples.
+    pub fn label(&self, label: &L) -> Option<&ClassReport<L>> {
+        self.labels.iter().find(|c| c.label == *label)
+    }
+}
+
+/// Computes the multi-label metrics of true and predicted sets of labels.
+///
+/// ### Arguments
+///
+/// * `y_true` - The true labels of each sample.
+/// * `y_pred` - The predicted labels of each sample.
+/// * `zero_division` - The policy for scores dividing by zero, e.g. the precision of a sample without predicted
+///   labels, or the Jaccard index of a sample without any label.
+///
+/// ### Returns
+///
+/// A `Result` containing the report if successful, `MetricError::LengthMismatch` if the lengths differ, or
+/// `MetricError::ZeroDivision` if the policy is `ZeroDivision::Error` and a division by zero occurs.
+///
+/// ### Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use text_score::classification::multilabel_report;
+/// use text_score::commons::ZeroDivision;
+///
+/// let y_true: Vec<HashSet<&str>> = vec![["sports", "politics"].into(), ["tech"].into()];
+/// let y_pred: Vec<HashSet<&str>> = vec![["sports"].into(), ["tech", "science"].into()];
+///
+/// let report = multilabel_report(&y_true, &y_pred, ZeroDivision::Zero).unwrap();
+/// assert_eq!(0.0, report.subset_accuracy);
+/// assert_eq!(0.25, report.hamming_loss);
+/// println!("micro F1: {}, samples F1: {}", report.micro_avg.f1, report.samples_avg.f1);
+/// ```
+///
+/// # Note
+///
+/// - The labels are those appearing in `y_true` or `y_pred`, so the Hamming loss does not count labels never seen.
+/// - As scikit-learn, the F1 score of a sample or a label is computed from its counts, so `zero_division` only
+///   applies to it if it has no true positives, false positives and false negatives.
+pub fn multilabel_report<L: Eq + Hash + Clone>(y_true: &[HashSet<L>], y_pred: &[HashSet<L>], zero_division: ZeroDivision) -> Result<MultiLabelReport<L>> {
+    if y_true.len() != y_pred.len() {
+        return Err(MetricError::LengthMismatch{inputs: y_pred.len(), references: y_true.len()});
+    }
+    let n_samples = y_true.len() as u32;
+
+    let mut labels: Vec<L> = Vec::new();
+    let mut label_counts: HashMap<L, ClassCounts> = HashMap::new();
+    let mut sample_scores: Vec<Score> = Vec::new();
+    let (mut errors, mut exact_matches, mut jaccard_sum): (u32, u32, f32) = (0, 0, 0.0);
+
+    for (true_set, predicted_set) in y_true.iter().zip(y_pred.iter()) {
+        let true_pos = true_set.intersection(predicted_set).count() as u32;
+        let false_pos = predicted_set.len() as u32 - true_pos;
+        let false_neg = true_set.len() as u32 - true_pos;
+        sample_scores.push(counts_score(ClassCounts{true_pos, false_pos, false_neg, true_neg: 0}, zero_division)?);
+        errors += false_pos + false_neg;
+        if false_pos + false_neg == 0 {
+            exact_matches += 1;
+        }
+        jaccard_sum += zero_division.divide(true_pos as f32, (true_pos + false_pos + false_neg) as f32)?;
+
+        for label in true_set.union(predicted_set) {
+            if !label_counts.contains_key(label) {
+                labels.push(label.clone());
+            }
+            let counts = label_counts.entry(label.clone()).or_default();
+            match (true_set.contains(label), predicted_set.contains(label)) {
+                (true, true) => counts.true_pos += 1,
+                (false, _) => counts.false_pos += 1,
+                (true, false) => counts.false_neg += 1,
+            }
+        }
+    }
+
+    let mut total = ClassCounts::default();
+    let mut classes: Vec<ClassReport<L>> = Vec::new();
+    for label in labels.into_iter() {
+        let mut counts = label_counts[&label];
+        counts.true_neg = n_samples - counts.true_pos - counts.false_pos - counts.false_neg;
+        total.true_pos += counts.true_pos;
+        total.false_pos += counts.false_pos;
+        total.false_neg += counts.false_neg;
+        classes.push(ClassReport{label, score: counts_score(counts, zero_division)?, support: counts.support()});
+    }
+
+    let scores: Vec<Score> = classes.iter().map(|c| c.score).collect();
+    let n_labels = classes.len() as f32;
+    Ok(MultiLabelReport{
+        samples_avg: weighted_mean(&sample_scores, &vec![1.0; sample_scores.len()], zero_division)?,
+        micro_avg: counts_score(total, zero_division)?,
+        macro_avg: weighted_mean(&scores, &vec![1.0; scores.len()], zero_division)?,
+        weighted_avg: weighted_mean(&scores, &classes.iter().map(|c| c.support as f32).collect::<Vec<f32>>(), zero_division)?,
+        hamming_loss: zero_division.divide(errors as f32, n_samples as f32 * n_labels)?,
+        subset_accuracy: zero_division.divide(exact_matches as f32, n_samples as f32)?,
+        jaccard: zero_division.divide(jaccard_sum, n_samples as f32)?,
+        labels: classes,
+        n_samples,
+    })
+}
@@ -1,8 +1,12 @@
+use std::io;
+use thiserror::Error;
+
 
 /// Represents precision, recall, and F1 score.
 ///
 /// The `Score` struct contains three floating-point fields: `precision`, `recall`, and `f1`.
 /// These fields represent evaluation metrics commonly used in natural language processing and information retrieval.
+/// `f1` holds the F-beta score for the `beta` the score was computed with, i.e. the F1 score if `beta` is 1.
 ///
 /// ### Examples
 /// ```
@@ -13,6 +17,7 @@
 ///     precision: 0.8,
 ///     recall: 0.7,
 ///     f1: 0.75,
+///     beta: 1.0,
 /// };
 ///
 /// // Access individual fields
@@ -20,17 +25,155 @@
 /// assert_eq!(score.recall, 0.7);
 /// assert_eq!(score.f1, 0.75);
 /// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Score{
     pub precision: f32,
     pub recall:f32,
     pub f1:f32,
+    /// The weight of recall relative to precision in `f1`.
+    pub beta:f32,
+}
+/// Specifies the value returned when a metric divides by zero, e.g. the precision of an empty prediction,
+/// as `zero_division` of scikit-learn.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::commons::{precision, precision_with_zero_division, ZeroDivision};
+///
+/// assert_eq!(0.0, precision(0, 0));
+/// assert_eq!(1.0, precision_with_zero_division(0, 0, ZeroDivision::One).unwrap());
+/// assert!(precision_with_zero_division(0, 0, ZeroDivision::NaN).unwrap().is_nan());
+/// assert!(precision_with_zero_division(0, 0, ZeroDivision::Error).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ZeroDivision {
+    /// Returns 0, the default.
+    #[default]
+    Zero,
+    /// Returns 1.
+    One,
+    /// Returns NaN.
+    NaN,
+    /// Returns `MetricError::ZeroDivision`.
+    Error,
 }
+
+impl ZeroDivision {
+    /// Returns `numerator / denominator`, or the value of the policy if `denominator` is 0.
+    pub fn divide(self, numerator: f32, denominator: f32) -> Result<f32> {
+        if denominator != 0.0 {
+            return Ok(numerator / denominator);
+        }
+        match self {
+            ZeroDivision::Zero => Ok(0.0),
+            ZeroDivision::One => Ok(1.0),
+            ZeroDivision::NaN => Ok(f32::NAN),
+            ZeroDivision::Error => Err(MetricError::ZeroDivision),
+        }
+    }
+}
+
+/// Returns `true_pos / (true_pos + false_pos)`, or 0 if there are no positive predictions.
 pub fn precision(true_pos:u32, false_pos:u32) -> f32{
-    return true_pos as f32 /((true_pos+false_pos) as f32);
+    precision_with_zero_division(true_pos, false_pos, ZeroDivision::Zero).unwrap_or(0.0)
 }
+/// Returns `true_pos / (true_pos + false_neg)`, or 0 if there are no positive labels.
 pub fn recall(true_pos:u32, false_neg:u32) -> f32{
-    return true_pos as f32 /((true_pos+false_neg) as f32);
+    recall_with_zero_division(true_pos, false_neg, ZeroDivision::Zero).unwrap_or(0.0)
 }
+/// Returns the harmonic mean of precision and recall, or 0 if both are 0.
 pub fn f1(precision: f32, recall: f32) -> f32{
-    return 2.0*(precision*recall)/(precision+recall);
-}
\ No newline at end of file
+    f_beta(precision, recall, 1.0)
+}
+/// Returns the weighted harmonic mean of precision and recall, where recall is `beta` times as important
+/// as precision, or 0 if both are 0.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::commons::{f1, f_beta};
+///
+/// assert_eq!(f1(0.5, 0.25), f_beta(0.5, 0.25, 1.0));
+/// // beta 2 is closer to recall, beta 0.5 to precision
+/// assert!(f_beta(0.5, 0.25, 2.0) < f1(0.5, 0.25));
+/// assert!(f_beta(0.5, 0.25, 0.5) > f1(0.5, 0.25));
+/// ```
+pub fn f_beta(precision: f32, recall: f32, beta: f32) -> f32{
+    f_beta_with_zero_division(precision, recall, beta, ZeroDivision::Zero).unwrap_or(0.0)
+}
+/// Computes precision, applying `zero_division` if there are no positive predictions.
+pub fn precision_with_zero_division(true_pos:u32, false_pos:u32, zero_division: ZeroDivision) -> Result<f32>{
+    zero_division.divide(true_pos as f32, (true_pos+false_pos) as f32)
+}
+/// Computes recall, applying `zero_division` if there are no positive labels.
+pub fn recall_with_zero_division(true_pos:u32, false_neg:u32, zero_division: ZeroDivision) -> Result<f32>{
+    zero_division.divide(true_pos as f32, (true_pos+false_neg) as f32)
+}
+/// Computes the harmonic mean of precision and recall, applying `zero_division` if both are 0.
+pub fn f1_with_zero_division(precision: f32, recall: f32, zero_division: ZeroDivision) -> Result<f32>{
+    f_beta_with_zero_division(precision, recall, 1.0, zero_division)
+}
+/// Computes the F-beta score of precision and recall, applying `zero_division` if both are 0.
+pub fn f_beta_with_zero_division(precision: f32, recall: f32, beta: f32, zero_division: ZeroDivision) -> Result<f32>{
+    let factor = beta*beta;
+    zero_division.divide((1.0+factor)*(precision*recall), factor*precision+recall)
+}
+
+/// The errors of the metrics, e.g. for invalid parameters or inputs.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::commons::MetricError;
+/// use text_score::rouge::rouge_n;
+///
+/// match rouge_n("a cat", "the cat", 0) {
+///     Err(MetricError::InvalidN(n)) => println!("invalid n-gram order: {}", n),
+///     Err(err) => println!("Error: {}", err),
+///     Ok(score) => println!("F1 Score: {}", score.f1),
+/// }
+/// ```
+#[derive(Debug, Error)]
+pub enum MetricError {
+    /// An n-gram order, e.g. `n` of ROUGE-N or `max_order` of BLEU, is less than 1.
+    #[error("n-gram order should be >= 1, got {0}")]
+    InvalidN(usize),
+    /// A weight is out of its range, e.g. a ROUGE-W weight less than 1 or NaN, a non-finite chrF beta,
+    /// or a negative or non-finite BLEU smoothing value.
+    #[error("weight out of range, got {0}")]
+    InvalidWeight(f32),
+    /// A parameter which should be a probability strictly between 0 and 1 is not.
+    #[error("{name} should be in (0, 1), got {value}")]
+    InvalidProbability{name: &'static str, value: f32},
+    /// A count, e.g. the number of bootstrap samples, is 0.
+    #[error("{0} should be >= 1")]
+    InvalidCount(&'static str),
+    /// A required list, e.g. the references, is empty.
+    #[error("{0} should not be empty")]
+    EmptyInput(&'static str),
+    /// The input at the given position of a corpus has no reference.
+    #[error("every input should have at least one reference, input {0} has none")]
+    MissingReference(usize),
+    /// The inputs and the references of a corpus have different lengths.
+    #[error("inputs and references should have the same length, got {inputs} and {references}")]
+    LengthMismatch{inputs: usize, references: usize},
+    /// A rouge type name is not one of `rouge1` to `rouge9`, `rougeL` or `rougeLsum`.
+    #[error("invalid rouge type: {0}")]
+    InvalidRougeType(String),
+    /// A sequence labeling tag is not valid in the tagging scheme, e.g. `E-PER` in BIO.
+    #[error("invalid tag for the scheme: {0}")]
+    InvalidTag(String),
+    /// A tokenizer pattern is not a valid regular expression.
+    #[error("invalid pattern: {0}")]
+    InvalidPattern(#[from] regex::Error),
+    /// A metric divides by zero and the `ZeroDivision` policy is `Error`.
+    #[error("division by zero, e.g. precision without predictions")]
+    ZeroDivision,
+    /// A file, e.g. of synonyms or stopwords, cannot be read.
+    #[error("cannot read file: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// The result of the fallible functions of the metrics.
+pub type Result<T> = std::result::Result<T, MetricError>;
@@ -0,0 +1,294 @@
+//! Edit distance based metrics, word error rate (WER) and character error rate (CER).
+//! The input is aligned to the reference with the Levenshtein distance, and the substitutions,
+//! deletions and insertions of the alignment are counted.
+//!
+use crate::commons::{MetricError, Result};
+use crate::tokenizer::{as_strs, Tokenizer, WhitespaceTokenizer};
+
+/// Represents an operation of the alignment from a reference to an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOperation {
+    /// The reference token is the same as the input token.
+    Hit,
+    /// The reference token is replaced by the input token.
+    Substitution,
+    /// The reference token is missing in the input.
+    Deletion,
+    /// The input token is not in the reference.
+    Insertion,
+}
+
+/// Represents an error rate together with the edit operations it is computed from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorRate {
+    /// `(substitutions + deletions + insertions) / reference_len`.
+    pub error_rate: f32,
+    /// The number of reference tokens matched by the same input token.
+    pub hits: u32,
+    /// The number of reference tokens replaced by a different input token.
+    pub substitutions: u32,
+    /// The number of reference tokens missing in the input.
+    pub deletions: u32,
+    /// The number of input tokens not in the reference.
+    pub insertions: u32,
+    /// The number of tokens in the reference.
+    pub reference_len: usize,
+}
+
+impl ErrorRate {
+    /// Counts the operations of an alignment.
+    fn from_operations(operations: &[EditOperation]) -> Self {
+        let count = |op: EditOperation| operations.iter().filter(|&&o| o == op).count() as u32;
+        let (hits, substitutions, deletions, insertions) = (
+            count(EditOperation::Hit),
+            count(EditOperation::Substitution),
+            count(EditOperation::Deletion),
+            count(EditOperation::Insertion),
+        );
+        let reference_len = (hits + substitutions + deletions) as usize;
+        let error_rate = (substitutions + deletions + insertions) as f32 / reference_len.max(1) as f32;
+
+        ErrorRate{error_rate, hits, substitutions, deletions, insertions, reference_len}
+    }
+
+    /// Sums the operations of several error rates and recomputes the rate over the total reference length.
+    fn sum(error_rates: &[ErrorRate]) -> Self {
+        let hits: u32 = error_rates.iter().map(|e| e.hits).sum();
+        let substitutions: u32 = error_rates.iter().map(|e| e.substitutions).sum();
+        let deletions: u32 = error_rates.iter().map(|e| e.deletions).sum();
+        let insertions: u32 = error_rates.iter().map(|e| e.insertions).sum();
+        let reference_len: usize = error_rates.iter().map(|e| e.reference_len).sum();
+        let error_rate = (substitutions + deletions + insertions) as f32 / reference_len.max(1) as f32;
+
+        ErrorRate{error_rate, hits, substitutions, deletions, insertions, reference_len}
+    }
+}
+
+/// Aligns an input token sequence to a reference token sequence with the minimum number of edits.
+///
+/// ### Arguments
+///
+/// * `input` - A slice of string slices representing the input tokens.
+/// * `reference` - A slice of string slices representing the reference tokens.
+///
+/// ### Returns
+///
+/// A `Vec` of `EditOperation`s in the order of the tokens, where the number of non-hit operations
+/// is the Levenshtein distance.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::edit_distance::{levenshtein_alignment, EditOperation};
+///
+/// let operations = levenshtein_alignment(&["the", "cat", "sat"], &["the", "cat", "sat", "down"]);
+/// assert_eq!(vec![EditOperation::Hit, EditOperation::Hit, EditOperation::Hit, EditOperation::Deletion], operations);
+/// ```
+///
+/// # Note
+///
+/// - When several alignments have the same distance, substitutions are preferred over deletions,
+///   and deletions over insertions.
+pub fn levenshtein_alignment(input: &[&str], reference: &[&str]) -> Vec<EditOperation> {
+    // distances[i][j]: the distance between reference[..i] and input[..j]
+    let mut distances: Vec<Vec<u32>> = vec![vec![0; input.len() + 1]; reference.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i as u32;
+    }
+    for (j, cell) in distances[0].iter_mut().enumerate() {
+        *cell = j as u32;
+    }
+    for i in 1..=reference.len() {
+        for j in 1..=input.len() {
+            let cost = if reference[i - 1] == input[j - 1] { 0 } else { 1 };
+            distances[i][j] = (distances[i - 1][j - 1] + cost)
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+        }
+    }
+
+    // backtrack from the end
+    let mut operations: Vec<EditOperation> = Vec::new();
+    let (mut i, mut j) = (reference.len(), input.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && reference[i - 1] == input[j - 1] && distances[i][j] == distances[i - 1][j - 1] {
+            operations.push(EditOperation::Hit);
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && j > 0 && distances[i][j] == distances[i - 1][j - 1] + 1 {
+            operations.push(EditOperation::Substitution);
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && distances[i][j] == distances[i - 1][j] + 1 {
+            operations.push(EditOperation::Deletion);
+            i -= 1;
+        } else {
+            operations.push(EditOperation::Insertion);
+            j -= 1;
+        }
+    }
+    operations.reverse();
+    operations
+}
+
+/// Splits a text into characters, with runs of whitespace collapsed into a single space.
+fn split_chars(text: &str) -> Vec<&str> {
+    let mut chars: Vec<&str> = Vec::new();
+    for (k, word) in text.split_whitespace().enumerate() {
+        if k > 0 {
+            chars.push(" ");
+        }
+        chars.extend(word.char_indices().map(|(i, c)| &word[i..i + c.len_utf8()]));
+    }
+    chars
+}
+
+/// Computes the word error rate (WER) of an input against a reference.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated, e.g. the output of a speech recognizer.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+///
+/// ### Returns
+///
+/// An `ErrorRate` struct with the rate and the counts of each edit operation.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::edit_distance::wer;
+///
+/// let result = wer("the cat sit on mat", "the cat sat on the mat");
+/// println!("WER: {}", result.error_rate); // 2/6
+/// println!("S: {}, D: {}, I: {}", result.substitutions, result.deletions, result.insertions); // 1, 1, 0
+/// ```
+///
+/// # Note
+///
+/// - The texts are split into words on whitespace, see `wer_with_tokenizer` for other tokenizers.
+/// - The rate can be greater than 1 if there are many insertions.
+/// - If the reference is empty, the rate is the number of insertions.
+pub fn wer(input: &str, reference: &str) -> ErrorRate {
+    wer_with_tokenizer(input, reference, &WhitespaceTokenizer)
+}
+
+/// Computes the word error rate (WER), splitting the texts into words with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+/// * `tokenizer` - The tokenizer splitting both texts into words.
+///
+/// ### Returns
+///
+/// An `ErrorRate` struct with the rate and the counts of each edit operation.
+pub fn wer_with_tokenizer(input: &str, reference: &str, tokenizer: &dyn Tokenizer) -> ErrorRate {
+    let input_tokens = tokenizer.tokenize(input);
+    let reference_tokens = tokenizer.tokenize(reference);
+
+    ErrorRate::from_operations(&levenshtein_alignment(&as_strs(&input_tokens), &as_strs(&reference_tokens)))
+}
+
+/// Computes the character error rate (CER) of an input against a reference.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+///
+/// ### Returns
+///
+/// An `ErrorRate` struct with the rate and the counts of each edit operation.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::edit_distance::cer;
+///
+/// let result = cer("kitten", "sitting");
+/// println!("CER: {}", result.error_rate); // 3/7
+/// ```
+///
+/// # Note
+///
+/// - Leading and trailing whitespace is ignored, and whitespace between words counts as a single space character.
+pub fn cer(input: &str, reference: &str) -> ErrorRate {
+    ErrorRate::from_operations(&levenshtein_alignment(&split_chars(input), &split_chars(reference)))
+}
+
+/// Computes the corpus-level word error rate of the given inputs against their references.
+///
+/// ### Arguments
+///
+/// * `inputs` - The input texts to be evaluated.
+/// * `references` - The reference texts, in the same order as `inputs`.
+///
+/// ### Returns
+///
+/// A `Result` containing an `ErrorRate` struct if successful, or an error message if the number of
+/// inputs and references differ.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::edit_distance::corpus_wer;
+///
+/// let result = corpus_wer(&["the cat sat", "hello"], &["the cat sat down", "hello world"]).unwrap();
+/// println!("WER: {}", result.error_rate); // 2/6
+/// ```
+///
+/// # Note
+///
+/// - The edit operations are summed over the corpus and divided by the total reference length,
+///   so this is not the average of sentence-level rates.
+pub fn corpus_wer(inputs: &[&str], references: &[&str]) -> Result<ErrorRate> {
+    corpus_wer_with_tokenizer(inputs, references, &WhitespaceTokenizer)
+}
+
+/// Computes the corpus-level word error rate, splitting the texts into words with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `inputs` - The input texts to be evaluated.
+/// * `references` - The reference texts, in the same order as `inputs`.
+/// * `tokenizer` - The tokenizer splitting every text into words.
+///
+/// ### Returns
+///
+/// A `Result` containing an `ErrorRate` struct if successful, or an error message if the number of
+/// inputs and references differ.
+pub fn corpus_wer_with_tokenizer(inputs: &[&str], references: &[&str], tokenizer: &dyn Tokenizer) -> Result<ErrorRate> {
+    if inputs.len() != references.len() {
+        return Err(MetricError::LengthMismatch{inputs: inputs.len(), references: references.len()});
+    }
+    let error_rates: Vec<ErrorRate> = inputs.iter().zip(references.iter()).map(|(i, r)| wer_with_tokenizer(i, r, tokenizer)).collect();
+
+    Ok(ErrorRate::sum(&error_rates))
+}
+
+/// Computes the corpus-level character error rate of the given inputs against their references.
+///
+/// ### Arguments
+///
+/// * `inputs` - The input texts to be evaluated.
+/// * `references` - The reference texts, in the same order as `inputs`.
+///
+/// ### Returns
+///
+/// A `Result` containing an `ErrorRate` struct if successful, or an error message if the number of
+/// inputs and references differ.
+///
+/// # Note
+///
+/// - The edit operations are summed over the corpus and divided by the total reference length,
+///   so this is not the average of sentence-level rates.
+pub fn corpus_cer(inputs: &[&str], references: &[&str]) -> Result<ErrorRate> {
+    if inputs.len() != references.len() {
+        return Err(MetricError::LengthMismatch{inputs: inputs.len(), references: references.len()});
+    }
+    let error_rates: Vec<ErrorRate> = inputs.iter().zip(references.iter()).map(|(i, r)| cer(i, r)).collect();
+
+    Ok(ErrorRate::sum(&error_rates))
+}
@@ -1,2 +1,14 @@
 pub mod rouge;
-pub mod commons;
\ No newline at end of file
+pub mod commons;
+pub mod scoring;
+pub mod bleu;
+pub mod chrf;
+pub mod edit_distance;
+pub mod ter;
+pub mod stemmer;
+pub mod meteor;
+pub mod tokenizer;
+pub mod normalizer;
+pub mod stopwords;
+pub mod classification;
+pub mod sequence_labeling;
\ No newline at end of file
@@ -0,0 +1,276 @@
+//! METEOR (Metric for Evaluation of Translation with Explicit ORdering), following Banerjee and Lavie (2005).
+//! Words of the input are aligned to the reference in stages: exact matches first, then matches of stems,
+//! then matches of synonyms. The stemmer and the synonyms are supplied by the caller, no WordNet is bundled.
+//!
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use crate::commons::Result;
+use crate::commons::{precision, recall};
+use crate::stemmer::Stemmer;
+use crate::tokenizer::{as_strs, Tokenizer, WhitespaceTokenizer};
+
+/// The number of partial alignments kept while searching for the alignment with the fewest chunks.
+const BEAM_WIDTH: usize = 20;
+
+/// Tells whether two words are synonyms.
+pub trait SynonymSource {
+    /// Returns `true` if `a` and `b` are synonyms.
+    fn are_synonyms(&self, a: &str, b: &str) -> bool;
+}
+
+/// A table of synonym sets, where each word can belong to several sets.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::meteor::{SynonymSource, SynonymTable};
+///
+/// let synonyms = SynonymTable::parse("big large huge\nsmall little");
+/// assert!(synonyms.are_synonyms("big", "huge"));
+/// assert!(!synonyms.are_synonyms("big", "little"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SynonymTable {
+    groups: HashMap<String, HashSet<usize>>,
+    n_groups: usize,
+}
+
+impl SynonymTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        SynonymTable::default()
+    }
+
+    /// Adds a set of words which are synonyms of each other.
+    pub fn add_group(&mut self, words: &[&str]) {
+        for word in words.iter() {
+            self.groups.entry(word.to_string()).or_default().insert(self.n_groups);
+        }
+        self.n_groups += 1;
+    }
+
+    /// Creates a table from a text with one synonym set per line, words separated by whitespace.
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Self {
+        let mut table = SynonymTable::new();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            table.add_group(&line.split_whitespace().collect::<Vec<&str>>());
+        }
+        table
+    }
+
+    /// Loads a table from a file in the format of `SynonymTable::parse`.
+    ///
+    /// ### Returns
+    ///
+    /// A `Result` containing the table if successful, or an error if the file cannot be read.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(SynonymTable::parse(&fs::read_to_string(path)?))
+    }
+}
+
+impl SynonymSource for SynonymTable {
+    fn are_synonyms(&self, a: &str, b: &str) -> bool {
+        match (self.groups.get(a), self.groups.get(b)) {
+            (Some(a_groups), Some(b_groups)) => !a_groups.is_disjoint(b_groups),
+            _ => false,
+        }
+    }
+}
+
+/// A (partial) alignment of input words to reference words.
+#[derive(Clone)]
+struct Alignment {
+    /// For each input word, the position of the reference word it is aligned to.
+    positions: Vec<Option<usize>>,
+    /// Whether each reference word is aligned.
+    used: Vec<bool>,
+    matches: u32,
+    /// The number of consecutive input words aligned to consecutive reference words.
+    adjacent: u32,
+}
+
+/// Represents a METEOR score together with the statistics it is computed from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeteorScore {
+    /// `fmean * (1 - penalty)`.
+    pub score: f32,
+    /// Unigram precision of the aligned words.
+    pub precision: f32,
+    /// Unigram recall of the aligned words.
+    pub recall: f32,
+    /// The weighted harmonic mean of precision and recall.
+    pub fmean: f32,
+    /// The fragmentation penalty.
+    pub penalty: f32,
+    /// The number of aligned words.
+    pub matches: u32,
+    /// The number of runs of aligned words which are adjacent in both the input and the reference.
+    pub chunks: u32,
+}
+
+/// Computes METEOR scores with optional stemming and synonym stages.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::meteor::{MeteorScorer, SynonymTable};
+///
+/// let scorer = MeteorScorer::new()
+///     .with_stemmer(|word: &str| word.trim_end_matches('s').to_string())
+///     .with_synonyms(SynonymTable::parse("sat seated"));
+///
+/// let score = scorer.score("the cats seated on the mat", "the cat sat on the mat");
+/// println!("METEOR: {}", score.score);
+/// ```
+pub struct MeteorScorer {
+    /// The weight of precision in the harmonic mean, recall is weighted by `1 - alpha`.
+    pub alpha: f32,
+    /// The exponent of the fragmentation.
+    pub beta: f32,
+    /// The maximum fragmentation penalty.
+    pub gamma: f32,
+    stemmer: Option<Box<dyn Stemmer>>,
+    synonyms: Option<Box<dyn SynonymSource>>,
+    tokenizer: Box<dyn Tokenizer>,
+}
+
+impl Default for MeteorScorer {
+    fn default() -> Self {
+        MeteorScorer{alpha: 0.9, beta: 3.0, gamma: 0.5, stemmer: None, synonyms: None, tokenizer: Box::new(WhitespaceTokenizer)}
+    }
+}
+
+impl MeteorScorer {
+    /// Creates a scorer with exact matching only, and the parameters of Banerjee and Lavie (2005):
+    /// `alpha` 0.9, `beta` 3 and `gamma` 0.5.
+    pub fn new() -> Self {
+        MeteorScorer::default()
+    }
+
+    /// Adds a stage matching words with the same stem.
+    pub fn with_stemmer<S: Stemmer + 'static>(mut self, stemmer: S) -> Self {
+        self.stemmer = Some(Box::new(stemmer));
+        self
+    }
+
+    /// Adds a stage matching synonyms.
+    pub fn with_synonyms<S: SynonymSource + 'static>(mut self, synonyms: S) -> Self {
+        self.synonyms = Some(Box::new(synonyms));
+        self
+    }
+
+    /// Splits the texts into words with the given tokenizer instead of on whitespace.
+    pub fn with_tokenizer<T: Tokenizer + 'static>(mut self, tokenizer: T) -> Self {
+        self.tokenizer = Box::new(tokenizer);
+        self
+    }
+
+    /// Aligns the input words to the reference words.
+    ///
+    /// ### Returns
+    ///
+    /// For each input word, the position of the reference word it is aligned to, if any.
+    fn align(&self, input: &[&str], reference: &[&str]) -> Vec<Option<usize>> {
+        let input_stems: Vec<String> = self.stemmer.as_ref().map_or(Vec::new(), |s| input.iter().map(|w| s.stem(w)).collect());
+        let reference_stems: Vec<String> = self.stemmer.as_ref().map_or(Vec::new(), |s| reference.iter().map(|w| s.stem(w)).collect());
+
+        let exact = |i: usize, j: usize| input[i] == reference[j];
+        let stem = |i: usize, j: usize| self.stemmer.is_some() && input_stems[i] == reference_stems[j];
+        let synonym = |i: usize, j: usize| self.synonyms.as_ref().is_some_and(|s| s.are_synonyms(input[i], reference[j]));
+        let stages: [&dyn Fn(usize, usize) -> bool; 3] = [&exact, &stem, &synonym];
+
+        let mut best = Alignment{positions: vec![None; input.len()], used: vec![false; reference.len()], matches: 0, adjacent: 0};
+        for stage in stages {
+            let mut beam: Vec<Alignment> = vec![best];
+            for i in 0..input.len() {
+                // aligned in an earlier stage, the same in every state
+                if beam[0].positions[i].is_some() {
+                    continue;
+                }
+
+                let mut next: Vec<Alignment> = Vec::new();
+                for state in beam.into_iter() {
+                    let candidates: Vec<usize> = (0..reference.len()).filter(|&j| !state.used[j] && stage(i, j)).collect();
+                    if candidates.is_empty() {
+                        next.push(state);
+                        continue;
+                    }
+                    for j in candidates {
+                        let mut aligned = state.clone();
+                        aligned.positions[i] = Some(j);
+                        aligned.used[j] = true;
+                        aligned.matches += 1;
+                        // links to the neighbours, the next word only if it was aligned in an earlier stage
+                        if i > 0 && j > 0 && aligned.positions[i - 1] == Some(j - 1) {
+                            aligned.adjacent += 1;
+                        }
+                        if i + 1 < input.len() && aligned.positions[i + 1] == Some(j + 1) {
+                            aligned.adjacent += 1;
+                        }
+                        next.push(aligned);
+                    }
+                }
+                // more matches first, then more adjacent words, i.e. fewer chunks
+                next.sort_by_key(|a| Reverse((a.matches, a.adjacent)));
+                next.truncate(BEAM_WIDTH);
+                beam = next;
+            }
+            best = beam.swap_remove(0);
+        }
+        best.positions
+    }
+
+    /// Computes the METEOR score of an input against a reference.
+    ///
+    /// ### Arguments
+    ///
+    /// * `input` - The input text to be evaluated.
+    /// * `reference` - The reference text, considered as the ground truth or gold standard.
+    ///
+    /// ### Returns
+    ///
+    /// A `MeteorScore` struct with the score and its statistics.
+    ///
+    /// # Note
+    ///
+    /// - The texts are tokenized into words with the tokenizer of the scorer, whitespace by default,
+    ///   and compared case-sensitively.
+    /// - In each stage, the words not aligned yet are aligned with a beam search that maximizes the number of
+    ///   matches first, then the number of adjacent words, so that the alignment has as few chunks as possible.
+    /// - `fmean = P * R / (alpha * P + (1 - alpha) * R)`, `penalty = gamma * (chunks / matches)^beta`.
+    pub fn score(&self, input: &str, reference: &str) -> MeteorScore {
+        let input_tokens = self.tokenizer.tokenize(input);
+        let reference_tokens = self.tokenizer.tokenize(reference);
+        let input_words: Vec<&str> = as_strs(&input_tokens);
+        let reference_words: Vec<&str> = as_strs(&reference_tokens);
+
+        let alignment = self.align(&input_words, &reference_words);
+        let matches = alignment.iter().flatten().count() as u32;
+        if matches == 0 {
+            return MeteorScore{score: 0.0, precision: 0.0, recall: 0.0, fmean: 0.0, penalty: 0.0, matches: 0, chunks: 0};
+        }
+
+        // a new chunk starts wherever the aligned words are not adjacent in both texts
+        let mut chunks: u32 = 0;
+        let mut previous: Option<(usize, usize)> = None;
+        for (i, j) in alignment.iter().enumerate().filter_map(|(i, j)| j.map(|j| (i, j))) {
+            if !matches!(previous, Some((pi, pj)) if pi + 1 == i && pj + 1 == j) {
+                chunks += 1;
+            }
+            previous = Some((i, j));
+        }
+
+        let p = precision(matches, input_words.len() as u32 - matches);
+        let r = recall(matches, reference_words.len() as u32 - matches);
+        let fmean = p * r / (self.alpha * p + (1.0 - self.alpha) * r);
+        let penalty = self.gamma * (chunks as f32 / matches as f32).powf(self.beta);
+
+        MeteorScore{score: fmean * (1.0 - penalty), precision: p, recall: r, fmean, penalty, matches, chunks}
+    }
+}
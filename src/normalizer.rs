@@ -0,0 +1,206 @@
+//! Text normalization applied before tokenization, e.g. case folding, Unicode normalization and punctuation removal.
+//!
+//! A `Normalizer` is configured once, and can be used with any metric either by wrapping its tokenizer
+//! (`Normalizer::tokenizer`) or by scoring normalized texts (`Normalizer::score`), which records the configuration
+//! together with the score. The signature of the configuration (`Normalizer::signature`) is meant to be reported
+//! along with the scores, as sacrebleu does, so that they can be reproduced.
+//!
+use std::fmt;
+use crate::commons::Result;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use crate::tokenizer::Tokenizer;
+
+/// The Unicode normalization forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnicodeForm {
+    /// Canonical composition, e.g. "e" followed by a combining acute accent becomes "é".
+    Nfc,
+    /// Compatibility composition, which additionally replaces e.g. "ﬁ" by "fi" and full-width letters by ASCII ones.
+    Nfkc,
+}
+
+/// Configuration of the text normalization. The default leaves texts untouched.
+///
+/// The steps are applied in the order of the fields.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::normalizer::{Normalizer, UnicodeForm};
+///
+/// let normalizer = Normalizer{
+///     unicode_form: Some(UnicodeForm::Nfkc),
+///     lowercase: true,
+///     remove_accents: true,
+///     remove_punctuation: true,
+///     collapse_whitespace: true,
+///     mask_digits: Some('0'),
+/// };
+/// assert_eq!("cafe au lait 00", normalizer.normalize("  Café au lait, 42! "));
+/// assert_eq!("lc:yes|form:nfkc|accents:remove|punct:remove|ws:collapse|digits:0", normalizer.signature());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Normalizer {
+    /// The Unicode normalization form, if any.
+    pub unicode_form: Option<UnicodeForm>,
+    /// Whether the text is lowercased, after the Unicode normalization so that e.g. "ℌ" becomes "h" with NFKC.
+    pub lowercase: bool,
+    /// Whether accents and other combining marks are removed, e.g. "é" becomes "e".
+    pub remove_accents: bool,
+    /// Whether punctuation and symbols, i.e. the characters neither alphanumeric, whitespace nor combining marks,
+    /// are removed. Combining marks are kept, so that accents of decomposed texts are only removed by `remove_accents`.
+    pub remove_punctuation: bool,
+    /// Whether leading and trailing whitespace is removed and runs of whitespace are replaced by a single space.
+    pub collapse_whitespace: bool,
+    /// The character replacing every digit, if any.
+    pub mask_digits: Option<char>,
+}
+
+impl Normalizer {
+    /// Creates a normalizer leaving texts untouched.
+    pub fn new() -> Self {
+        Normalizer::default()
+    }
+
+    /// Normalizes a text.
+    ///
+    /// ### Arguments
+    ///
+    /// * `text` - The text to be normalized.
+    ///
+    /// ### Returns
+    ///
+    /// The normalized text.
+    pub fn normalize(&self, text: &str) -> String {
+        let mut normalized: String = match self.unicode_form {
+            Some(UnicodeForm::Nfc) => text.nfc().collect(),
+            Some(UnicodeForm::Nfkc) => text.nfkc().collect(),
+            None => text.to_string(),
+        };
+        if self.lowercase {
+            normalized = normalized.to_lowercase();
+        }
+        if self.remove_accents {
+            // decompose, drop the combining marks, and compose again
+            normalized = normalized.nfd().filter(|&c| !is_combining_mark(c)).nfc().collect();
+        }
+        if self.remove_punctuation {
+            normalized.retain(|c| c.is_alphanumeric() || c.is_whitespace() || is_combining_mark(c));
+        }
+        if self.collapse_whitespace {
+            normalized = normalized.split_whitespace().collect::<Vec<&str>>().join(" ");
+        }
+        if let Some(mask) = self.mask_digits {
+            normalized = normalized.chars().map(|c| if c.is_numeric() { mask } else { c }).collect();
+        }
+        normalized
+    }
+
+    /// Returns a short description of the configuration, to be reported along with the scores.
+    pub fn signature(&self) -> String {
+        let yes_no = |b: bool| if b { "yes" } else { "no" };
+        let form = match self.unicode_form {
+            Some(UnicodeForm::Nfc) => "nfc",
+            Some(UnicodeForm::Nfkc) => "nfkc",
+            None => "none",
+        };
+        let digits = self.mask_digits.map_or("keep".to_string(), |mask| mask.to_string());
+        format!(
+            "lc:{}|form:{}|accents:{}|punct:{}|ws:{}|digits:{}",
+            yes_no(self.lowercase),
+            form,
+            if self.remove_accents { "remove" } else { "keep" },
+            if self.remove_punctuation { "remove" } else { "keep" },
+            if self.collapse_whitespace { "collapse" } else { "keep" },
+            digits,
+        )
+    }
+
+    /// Wraps a tokenizer so that texts are normalized before they are tokenized.
+    ///
+    /// Metrics which do not tokenize the whole text are only partly normalized this way, e.g. the character
+    /// n-grams of chrF are built from the raw text and only its word n-grams are tokenized.
+    /// Use `Normalizer::score` for those.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use text_score::normalizer::Normalizer;
+    /// use text_score::rouge::rouge_n_with_tokenizer;
+    /// use text_score::tokenizer::WhitespaceTokenizer;
+    ///
+    /// let normalizer = Normalizer{lowercase: true, remove_punctuation: true, ..Normalizer::default()};
+    /// let tokenizer = normalizer.tokenizer(WhitespaceTokenizer);
+    /// let score = rouge_n_with_tokenizer("This is a case.", "this is a case", 1, &tokenizer).unwrap();
+    /// assert_eq!(1.0, score.f1);
+    /// ```
+    pub fn tokenizer<T: Tokenizer>(&self, tokenizer: T) -> NormalizingTokenizer<T> {
+        NormalizingTokenizer{normalizer: *self, tokenizer}
+    }
+
+    /// Normalizes an input and a reference, and scores them with the given metric.
+    ///
+    /// ### Arguments
+    ///
+    /// * `input` - The input text to be evaluated.
+    /// * `reference` - The reference text, considered as the ground truth or gold standard.
+    /// * `scorer` - A function computing the score of the normalized input against the normalized reference.
+    ///
+    /// ### Returns
+    ///
+    /// A `Result` containing the score together with the normalizer if successful, or the error of `scorer`.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use text_score::normalizer::Normalizer;
+    /// use text_score::rouge::rouge_n;
+    ///
+    /// let normalizer = Normalizer{lowercase: true, remove_punctuation: true, ..Normalizer::default()};
+    /// let result = normalizer.score("This is a case.", "this is a case", |i, r| rouge_n(i, r, 1)).unwrap();
+    /// assert_eq!(1.0, result.score.f1);
+    /// println!("rouge-1: {} ({})", result.score.f1, result.normalizer.signature());
+    ///
+    /// // chrF character n-grams are normalized as well
+    /// use text_score::chrf::{sentence_chrf, ChrfConfig};
+    /// let result = normalizer.score("This is a case.", "this is a case", |i, r| sentence_chrf(i, &[r], &ChrfConfig::default())).unwrap();
+    /// assert_eq!(1.0, result.score.f1);
+    /// ```
+    pub fn score<S, F>(&self, input: &str, reference: &str, scorer: F) -> Result<Normalized<S>>
+    where
+        F: Fn(&str, &str) -> Result<S>,
+    {
+        let score = scorer(&self.normalize(input), &self.normalize(reference))?;
+        Ok(Normalized{score, normalizer: *self})
+    }
+}
+
+impl fmt::Display for Normalizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.signature())
+    }
+}
+
+/// A tokenizer normalizing texts before tokenizing them, see `Normalizer::tokenizer`.
+#[derive(Debug, Clone)]
+pub struct NormalizingTokenizer<T: Tokenizer> {
+    /// The normalization applied first.
+    pub normalizer: Normalizer,
+    tokenizer: T,
+}
+
+impl<T: Tokenizer> Tokenizer for NormalizingTokenizer<T> {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.tokenizer.tokenize(&self.normalizer.normalize(text))
+    }
+}
+
+/// Represents a score computed from normalized texts, together with the normalization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normalized<S> {
+    /// The score of the normalized texts.
+    pub score: S,
+    /// The normalization applied to the texts.
+    pub normalizer: Normalizer,
+}
@@ -1,10 +1,16 @@
 //! This is an implementation for metrics to be used in various ML/DL fields.
-//! for now, split_whitespace based rouge-n score is provided.
+//! for now, rouge-n, rouge-l, rouge-lsum, rouge-s(u) and rouge-w scores are provided.
+//! `RougeScorer` computes several of them at once, as `rouge_score` does.
+//! The texts are split on whitespace by default, and every score has a `_with_tokenizer` variant taking a `Tokenizer`.
 //!
 use std::collections::HashMap;
 use std::cmp::{min, max};
-use anyhow::{Result, Error};
-pub use crate::commons::{Score, f1, precision, recall};
+use std::fmt;
+use std::str::FromStr;
+use crate::commons::{MetricError, Result};
+pub use crate::commons::{Score, ZeroDivision, f1, f_beta, precision, recall};
+use crate::stemmer::Stemmer;
+use crate::tokenizer::{as_strs, RougeTokenizer, Tokenizer, WhitespaceTokenizer, ROUGE_MIN_STEM_LEN};
 
 
 
@@ -43,14 +49,16 @@ pub use crate::commons::{Score, f1, precision, recall};
 ///   and create n-grams of the specified size `n`.
 /// - The resulting n-grams are stored in a `HashMap`, where each key is an n-gram,
 ///   and the corresponding value is the count of occurrences of that n-gram in the input sequence.
+/// - If there are fewer than `n` tokens, e.g. for an empty text, the `HashMap` is empty.
 pub fn create_ngrams(tokens: Vec<&str>, n: usize) -> HashMap<Vec<&str>, u32> {
     let mut ngrams: HashMap<Vec<&str>, u32> = HashMap::new();
 
-    for i in 0..(tokens.len() - n + 1) {
+    // no n-grams if there are fewer than n tokens
+    for i in 0..(tokens.len() + 1).saturating_sub(n) {
         let ngram: Vec<&str> = tokens[i..i + n].to_vec();
         *ngrams.entry(ngram).or_insert(0) += 1;
     }
-    return ngrams;
+    ngrams
 }
 
 /// Computes precision, recall, and F1 score based on n-grams.
@@ -62,10 +70,11 @@ pub fn create_ngrams(tokens: Vec<&str>, n: usize) -> HashMap<Vec<&str>, u32> {
 ///
 /// * `predicted_ngrams` - A HashMap containing n-grams and their counts for the predicted sequence.
 /// * `target_ngrams` - A HashMap containing n-grams and their counts for the target (reference) sequence.
+/// * `beta` - The weight of recall relative to precision, 1 for the F1 score.
 ///
 /// ### Returns
 ///
-/// A `Score` struct containing precision, recall, and F1 score for the prediction based on n-grams.
+/// A `Score` struct containing precision, recall, and F-beta score for the prediction based on n-grams.
 ///
 /// ### Examples
 ///
@@ -76,7 +85,7 @@ pub fn create_ngrams(tokens: Vec<&str>, n: usize) -> HashMap<Vec<&str>, u32> {
 /// let predicted_ngrams = hashmap! { vec!["this", "is"] => 2, vec!["is", "an"] => 1 };
 /// let target_ngrams = hashmap! { vec!["this", "is"] => 3, vec!["is", "an"] => 2 };
 ///
-/// let score = ngram_based_score(predicted_ngrams, target_ngrams);
+/// let score = ngram_based_score(predicted_ngrams, target_ngrams, 1.0);
 /// println!("Precision: {}", score.precision); // Accessing precision field
 /// println!("Recall: {}", score.recall);       // Accessing recall field
 /// println!("F1 Score: {}", score.f1);         // Accessing f1 field
@@ -86,23 +95,64 @@ pub fn create_ngrams(tokens: Vec<&str>, n: usize) -> HashMap<Vec<&str>, u32> {
 ///
 /// - The function iterates through the target n-grams and computes the intersection count
 ///   with the predicted n-grams to calculate precision, recall, and F1 score.
-/// - Precision and recall are calculated using the standard formulas, and F1 score is computed
-///   using the `f1` function defined in the module.
+/// - Precision and recall are calculated using the standard formulas, and F-beta score is computed
+///   using the `f_beta` function defined in the module.
 /// - The resulting scores are returned in a `Score` struct.
-pub fn ngram_based_score(predicted_ngrams:HashMap<Vec<&str>, u32>, target_ngrams:HashMap<Vec<&str>, u32>) -> Score{
+pub fn ngram_based_score(predicted_ngrams:HashMap<Vec<&str>, u32>, target_ngrams:HashMap<Vec<&str>, u32>, beta: f32) -> Score{
+    ngram_based_score_with_zero_division(predicted_ngrams, target_ngrams, beta, ZeroDivision::Zero)
+        .unwrap_or(Score{precision:0.0, recall:0.0, f1:0.0, beta})
+}
+
+/// Computes precision, recall, and F1 score based on n-grams, applying `zero_division` when there are no n-grams.
+///
+/// ### Arguments
+///
+/// * `predicted_ngrams` - A HashMap containing n-grams and their counts for the predicted sequence.
+/// * `target_ngrams` - A HashMap containing n-grams and their counts for the target (reference) sequence.
+/// * `beta` - The weight of recall relative to precision, 1 for the F1 score.
+/// * `zero_division` - The policy for the precision of a prediction without n-grams, the recall of a target
+///   without n-grams, and the F1 score if both have none.
+///
+/// ### Returns
+///
+/// A `Result` containing a `Score` struct if successful, or `MetricError::ZeroDivision` if the policy is
+/// `ZeroDivision::Error` and a division by zero occurs.
+///
+/// ### Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use text_score::rouge::{create_ngrams, ngram_based_score_with_zero_division, ZeroDivision};
+///
+/// // empty prediction: precision is the value of the policy, recall and F1 are 0
+/// let score = ngram_based_score_with_zero_division(HashMap::new(), create_ngrams(vec!["a", "b"], 1), 1.0, ZeroDivision::One).unwrap();
+/// assert_eq!((1.0, 0.0, 0.0), (score.precision, score.recall, score.f1));
+/// ```
+///
+/// # Note
+///
+/// - If either side has no n-grams, F-beta is computed from the counts as
+///   `(1 + beta^2) * intersection / (beta^2 * target + predicted)`, as scikit-learn does,
+///   so it is 0 and not the value of the policy if only one side has no n-grams.
+pub fn ngram_based_score_with_zero_division(predicted_ngrams:HashMap<Vec<&str>, u32>, target_ngrams:HashMap<Vec<&str>, u32>, beta: f32, zero_division: ZeroDivision) -> Result<Score>{
     let mut intersection_ngrams_count: u32=0;
-    let target_ngrams_count:u32 = target_ngrams.values().map(|&v| v).sum();
-    let prediction_ngrams_count:u32= predicted_ngrams.values().map(|&v| v).sum();
+    let target_ngrams_count:u32 = target_ngrams.values().copied().sum();
+    let prediction_ngrams_count:u32= predicted_ngrams.values().copied().sum();
 
     for (ngram, target_cnt) in target_ngrams.iter(){
         intersection_ngrams_count += min(target_cnt, predicted_ngrams.get(ngram).unwrap_or(&0));
 
     }
-    let p:f32 = intersection_ngrams_count as f32/ max(prediction_ngrams_count, 1) as f32;
-    let r:f32 = intersection_ngrams_count as f32/ max(target_ngrams_count, 1) as f32;
-    let f:f32 = f1(p, r);
+    let p:f32 = zero_division.divide(intersection_ngrams_count as f32, prediction_ngrams_count as f32)?;
+    let r:f32 = zero_division.divide(intersection_ngrams_count as f32, target_ngrams_count as f32)?;
+    let f:f32 = if prediction_ngrams_count == 0 || target_ngrams_count == 0 {
+        let factor = beta * beta;
+        zero_division.divide((1.0 + factor) * intersection_ngrams_count as f32, factor * target_ngrams_count as f32 + prediction_ngrams_count as f32)?
+    } else {
+        f_beta(p, r, beta)
+    };
 
-    return Score{precision:p, recall:r, f1:f};
+    Ok(Score{precision:p, recall:r, f1:f, beta})
 }
 
 
@@ -149,17 +199,813 @@ pub fn ngram_based_score(predicted_ngrams:HashMap<Vec<&str>, u32>, target_ngrams
 /// - The n-gram based scores are then calculated using the `ngram_based_score` function.
 /// - The resulting scores are returned in a `Score` struct if the operation is successful.
 pub fn rouge_n(input:&str, reference: &str, n:usize) -> Result<Score>{
+    rouge_n_with_tokenizer(input, reference, n, &WhitespaceTokenizer)
+}
+
+/// Computes ROUGE-N scores, splitting the texts into tokens with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+/// * `n` - The size of n-grams to be used in the evaluation.
+/// * `tokenizer` - The tokenizer splitting both texts into tokens.
+///
+/// ### Returns
+///
+/// A `Result` containing a `Score` struct if successful, or an error message if `n` is less than 1.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::rouge_n_with_tokenizer;
+/// use text_score::tokenizer::RougeTokenizer;
+///
+/// // same as rouge_score: case and punctuation are ignored
+/// let score = rouge_n_with_tokenizer("This is identical case.", "this is identical case", 1, &RougeTokenizer).unwrap();
+/// assert_eq!(1.0, score.f1);
+/// ```
+pub fn rouge_n_with_tokenizer(input:&str, reference: &str, n:usize, tokenizer: &dyn Tokenizer) -> Result<Score>{
     if n < 1 {
-        return Err(Error::msg("n should be >= 1"));
+        return Err(MetricError::InvalidN(n));
     }
 
-    let input_words = input.split_whitespace().collect();
-    let reference_words = reference.split_whitespace().collect();
+    let input_tokens = tokenizer.tokenize(input);
+    let reference_tokens = tokenizer.tokenize(reference);
 
     // create n-grams
-    let mut input_ngrams = create_ngrams(input_words, n);
-    let mut reference_ngrams = create_ngrams(reference_words, n);
+    let input_ngrams = create_ngrams(as_strs(&input_tokens), n);
+    let reference_ngrams = create_ngrams(as_strs(&reference_tokens), n);
 
     // get n-gram based f1 score
-    Ok(ngram_based_score(input_ngrams, reference_ngrams))
+    Ok(ngram_based_score(input_ngrams, reference_ngrams, 1.0))
+}
+
+/// Computes the dynamic programming table for the longest common subsequence (LCS).
+///
+/// Given two token sequences `a` and `b`, this function fills a `(a.len() + 1) x (b.len() + 1)`
+/// table where the cell `[i][j]` holds the LCS length of `a[..i]` and `b[..j]`.
+///
+/// ### Arguments
+///
+/// * `a` - A slice of string slices representing the first token sequence.
+/// * `b` - A slice of string slices representing the second token sequence.
+///
+/// ### Returns
+///
+/// A 2-dimensional `Vec` whose last cell `[a.len()][b.len()]` is the LCS length of `a` and `b`.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::lcs_table;
+///
+/// let a = vec!["police", "killed", "the", "gunman"];
+/// let b = vec!["police", "kill", "the", "gunman"];
+///
+/// let table = lcs_table(&a, &b);
+/// assert_eq!(3, table[a.len()][b.len()]);
+/// ```
+pub fn lcs_table(a: &[&str], b: &[&str]) -> Vec<Vec<u32>> {
+    let mut table: Vec<Vec<u32>> = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            table[i][j] = if a[i - 1] == b[j - 1] {
+                table[i - 1][j - 1] + 1
+            } else {
+                max(table[i - 1][j], table[i][j - 1])
+            };
+        }
+    }
+    table
+}
+
+/// Computes sentence-level ROUGE-L scores for a given input and reference text.
+///
+/// ROUGE-L measures the longest common subsequence (LCS) between the input and the reference.
+/// Unlike `rouge_n`, the matched words do not have to be consecutive, they only have to appear
+/// in the same order, so sentence level structure is captured without a predefined n-gram size.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+///
+/// ### Returns
+///
+/// A `Score` struct where precision is `LCS / len(input)`, recall is `LCS / len(reference)`
+/// and f1 is the harmonic mean of the two.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::rouge_l;
+///
+/// let score = rouge_l("police killed the gunman", "police kill the gunman");
+/// println!("Precision: {}", score.precision); // 3/4
+/// println!("Recall: {}", score.recall);       // 3/4
+/// println!("F1 Score: {}", score.f1);         // 3/4
+/// ```
+///
+/// # Note
+///
+/// - The input and reference texts are split into words on whitespace, see the `_with_tokenizer` variant for other tokenizers.
+/// - The LCS length is computed with the `lcs_table` function.
+pub fn rouge_l(input: &str, reference: &str) -> Score {
+    rouge_l_with_tokenizer(input, reference, &WhitespaceTokenizer)
+}
+
+/// Computes sentence-level ROUGE-L scores, splitting the texts into tokens with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+/// * `tokenizer` - The tokenizer splitting both texts into tokens.
+///
+/// ### Returns
+///
+/// A `Score` struct, see `rouge_l`.
+pub fn rouge_l_with_tokenizer(input: &str, reference: &str, tokenizer: &dyn Tokenizer) -> Score {
+    let input_tokens = tokenizer.tokenize(input);
+    let reference_tokens = tokenizer.tokenize(reference);
+    lcs_score(&as_strs(&input_tokens), &as_strs(&reference_tokens))
+}
+
+/// Computes ROUGE-L scores of tokenized texts.
+fn lcs_score(input_words: &[&str], reference_words: &[&str]) -> Score {
+    let lcs = lcs_table(input_words, reference_words)[input_words.len()][reference_words.len()];
+
+    let p: f32 = lcs as f32 / max(input_words.len(), 1) as f32;
+    let r: f32 = lcs as f32 / max(reference_words.len(), 1) as f32;
+    let f: f32 = f1(p, r);
+
+    Score{precision:p, recall:r, f1:f, beta:1.0}
+}
+
+/// Finds the token indices of the longest common subsequence (LCS) in the reference.
+///
+/// The LCS table of `reference` and `input` is built with `lcs_table` and then backtracked
+/// to collect which positions of `reference` take part in the LCS.
+///
+/// ### Arguments
+///
+/// * `reference` - A slice of string slices representing the reference sentence.
+/// * `input` - A slice of string slices representing the input sentence.
+///
+/// ### Returns
+///
+/// A `Vec` of indices into `reference`, in ascending order.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::lcs_indices;
+///
+/// let reference = vec!["w1", "w2", "w3", "w4", "w5"];
+/// let input = vec!["w1", "w3", "w8", "w9", "w5"];
+///
+/// assert_eq!(vec![0, 2, 4], lcs_indices(&reference, &input));
+/// ```
+pub fn lcs_indices(reference: &[&str], input: &[&str]) -> Vec<usize> {
+    let table = lcs_table(reference, input);
+    let mut indices: Vec<usize> = Vec::new();
+    let (mut i, mut j) = (reference.len(), input.len());
+
+    while i > 0 && j > 0 {
+        if reference[i - 1] == input[j - 1] {
+            indices.push(i - 1);
+            i -= 1;
+            j -= 1;
+        } else if table[i][j - 1] > table[i - 1][j] {
+            j -= 1;
+        } else {
+            i -= 1;
+        }
+    }
+    indices.reverse();
+    indices
+}
+
+/// Computes summary-level ROUGE-Lsum scores for a given input and reference text.
+///
+/// Both texts are split into sentences on newlines. For every reference sentence, the union of
+/// its LCS matches against all input sentences is taken (union-LCS), and every matched token is
+/// counted as a hit as long as it has not been used up in the input or the reference.
+/// This follows `_summary_level_lcs` of the google research implementation.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated, one sentence per line.
+/// * `reference` - The reference text, one sentence per line.
+///
+/// ### Returns
+///
+/// A `Score` struct where precision is `hits / len(input)`, recall is `hits / len(reference)`
+/// and f1 is the harmonic mean of the two.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::rouge_lsum;
+///
+/// let score = rouge_lsum("w1 w2 w6 w7 w8\nw1 w3 w8 w9 w5", "w1 w2 w3 w4 w5");
+/// println!("Precision: {}", score.precision); // 0.4
+/// println!("Recall: {}", score.recall);       // 0.8
+/// println!("F1 Score: {}", score.f1);         // 0.5333
+/// ```
+///
+/// # Note
+///
+/// - Empty lines are ignored, and each sentence is split into words on whitespace.
+/// - If either text has no tokens, all scores are 0.
+pub fn rouge_lsum(input: &str, reference: &str) -> Score {
+    rouge_lsum_with_tokenizer(input, reference, &WhitespaceTokenizer)
+}
+
+/// Computes summary-level ROUGE-Lsum scores, splitting each sentence into tokens with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated, one sentence per line.
+/// * `reference` - The reference text, one sentence per line.
+/// * `tokenizer` - The tokenizer splitting every sentence into tokens.
+///
+/// ### Returns
+///
+/// A `Score` struct, see `rouge_lsum`.
+pub fn rouge_lsum_with_tokenizer(input: &str, reference: &str, tokenizer: &dyn Tokenizer) -> Score {
+    let input_tokens: Vec<Vec<String>> = split_sentences(input, tokenizer);
+    let reference_tokens: Vec<Vec<String>> = split_sentences(reference, tokenizer);
+    let input_sentences: Vec<Vec<&str>> = input_tokens.iter().map(|s| as_strs(s)).collect();
+    let reference_sentences: Vec<Vec<&str>> = reference_tokens.iter().map(|s| as_strs(s)).collect();
+    summary_lcs_score(&input_sentences, &reference_sentences)
+}
+
+/// Computes ROUGE-Lsum scores of texts split into sentences of tokens.
+fn summary_lcs_score(input_sentences: &[Vec<&str>], reference_sentences: &[Vec<&str>]) -> Score {
+    let input_len: usize = input_sentences.iter().map(|s| s.len()).sum();
+    let reference_len: usize = reference_sentences.iter().map(|s| s.len()).sum();
+    if input_len == 0 || reference_len == 0 {
+        return Score{precision:0.0, recall:0.0, f1:0.0, beta:1.0};
+    }
+
+    let mut input_counts: HashMap<&str, u32> = HashMap::new();
+    let mut reference_counts: HashMap<&str, u32> = HashMap::new();
+    for token in input_sentences.iter().flatten() {
+        *input_counts.entry(token).or_insert(0) += 1;
+    }
+    for token in reference_sentences.iter().flatten() {
+        *reference_counts.entry(token).or_insert(0) += 1;
+    }
+
+    let mut hits: u32 = 0;
+    for reference_sentence in reference_sentences.iter() {
+        // union of the LCS indices against every input sentence
+        let mut union: Vec<usize> = input_sentences
+            .iter()
+            .flat_map(|input_sentence| lcs_indices(reference_sentence, input_sentence))
+            .collect();
+        union.sort_unstable();
+        union.dedup();
+
+        for token in union.into_iter().map(|i| reference_sentence[i]) {
+            let input_cnt = input_counts.entry(token).or_insert(0);
+            let reference_cnt = reference_counts.entry(token).or_insert(0);
+            if *input_cnt > 0 && *reference_cnt > 0 {
+                hits += 1;
+                *input_cnt -= 1;
+                *reference_cnt -= 1;
+            }
+        }
+    }
+
+    let p: f32 = hits as f32 / input_len as f32;
+    let r: f32 = hits as f32 / reference_len as f32;
+    let f: f32 = f1(p, r);
+
+    Score{precision:p, recall:r, f1:f, beta:1.0}
+}
+
+/// Splits a text into newline separated sentences of tokens, skipping sentences without any token.
+fn split_sentences(text: &str, tokenizer: &dyn Tokenizer) -> Vec<Vec<String>> {
+    text.lines()
+        .map(|line| tokenizer.tokenize(line))
+        .filter(|tokens| !tokens.is_empty())
+        .collect()
+}
+
+/// Creates skip-bigrams from a list of tokens.
+///
+/// A skip-bigram is any pair of tokens in their sentence order, allowing arbitrary gaps in between.
+/// The gap can be limited with `max_skip`, e.g. `Some(4)` gives the skip-bigrams used by ROUGE-S4.
+///
+/// ### Arguments
+///
+/// * `tokens` - A vector of string slices representing individual tokens.
+/// * `max_skip` - The maximum number of tokens allowed between the two words of a pair.
+///   `None` means there is no limit.
+///
+/// ### Returns
+///
+/// A `HashMap` where keys are skip-bigrams (represented as vectors of two string slices) and values
+/// are the counts of each skip-bigram in the input sequence.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::create_skip_bigrams;
+///
+/// let tokens = vec!["police", "killed", "the", "gunman"];
+///
+/// // 6 pairs: "police killed", "police the", "police gunman", "killed the", "killed gunman", "the gunman"
+/// let skip_bigrams = create_skip_bigrams(tokens.clone(), None);
+/// assert_eq!(6, skip_bigrams.len());
+///
+/// // with max_skip 0, skip-bigrams are the same as bigrams.
+/// let skip_bigrams = create_skip_bigrams(tokens, Some(0));
+/// assert_eq!(3, skip_bigrams.len());
+/// ```
+pub fn create_skip_bigrams(tokens: Vec<&str>, max_skip: Option<usize>) -> HashMap<Vec<&str>, u32> {
+    let mut skip_bigrams: HashMap<Vec<&str>, u32> = HashMap::new();
+
+    for i in 0..tokens.len() {
+        let last = match max_skip {
+            Some(skip) => min(i.saturating_add(skip).saturating_add(1), tokens.len() - 1),
+            None => tokens.len() - 1,
+        };
+        for j in (i + 1)..=last {
+            *skip_bigrams.entry(vec![tokens[i], tokens[j]]).or_insert(0) += 1;
+        }
+    }
+    skip_bigrams
+}
+
+/// Computes ROUGE-S scores based on skip-bigrams for a given input and reference text.
+///
+/// ROUGE-S counts the overlap of skip-bigrams, i.e. pairs of words in sentence order
+/// with arbitrary gaps, between the input and the reference.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+/// * `max_skip` - The maximum skip distance, e.g. `Some(4)` for ROUGE-S4. `None` means there is no limit.
+///
+/// ### Returns
+///
+/// A `Score` struct containing precision, recall, and F1 score based on skip-bigrams.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::rouge_s;
+///
+/// let score = rouge_s("police killed the gunman", "police kill the gunman", None);
+/// println!("F1 Score: {}", score.f1); // 3/6
+/// ```
+///
+/// # Note
+///
+/// - The input and reference texts are split into words on whitespace, see the `_with_tokenizer` variant for other tokenizers.
+/// - The skip-bigram based scores are calculated using the `ngram_based_score` function.
+pub fn rouge_s(input: &str, reference: &str, max_skip: Option<usize>) -> Score {
+    rouge_s_with_tokenizer(input, reference, max_skip, &WhitespaceTokenizer)
+}
+
+/// Computes ROUGE-S scores, splitting the texts into tokens with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+/// * `max_skip` - The maximum skip distance, e.g. `Some(4)` for ROUGE-S4. `None` means there is no limit.
+/// * `tokenizer` - The tokenizer splitting both texts into tokens.
+///
+/// ### Returns
+///
+/// A `Score` struct containing precision, recall, and F1 score based on skip-bigrams.
+pub fn rouge_s_with_tokenizer(input: &str, reference: &str, max_skip: Option<usize>, tokenizer: &dyn Tokenizer) -> Score {
+    let input_tokens = tokenizer.tokenize(input);
+    let reference_tokens = tokenizer.tokenize(reference);
+    let input_skip_bigrams = create_skip_bigrams(as_strs(&input_tokens), max_skip);
+    let reference_skip_bigrams = create_skip_bigrams(as_strs(&reference_tokens), max_skip);
+
+    ngram_based_score(input_skip_bigrams, reference_skip_bigrams, 1.0)
+}
+
+/// Computes ROUGE-SU scores based on skip-bigrams and unigrams for a given input and reference text.
+///
+/// ROUGE-SU extends ROUGE-S with unigram matches, so that a sentence without any
+/// skip-bigram in common with the reference still gets credit for the words it shares.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+/// * `max_skip` - The maximum skip distance, e.g. `Some(4)` for ROUGE-SU4. `None` means there is no limit.
+///
+/// ### Returns
+///
+/// A `Score` struct containing precision, recall, and F1 score based on skip-bigrams and unigrams.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::rouge_su;
+///
+/// let score = rouge_su("gunman the killed police", "police killed the gunman", None);
+/// println!("F1 Score: {}", score.f1); // 4/10
+/// ```
+///
+/// # Note
+///
+/// - Skip-bigrams are created with `create_skip_bigrams` and unigrams with `create_ngrams`,
+///   and both are counted together by `ngram_based_score`.
+pub fn rouge_su(input: &str, reference: &str, max_skip: Option<usize>) -> Score {
+    rouge_su_with_tokenizer(input, reference, max_skip, &WhitespaceTokenizer)
+}
+
+/// Computes ROUGE-SU scores, splitting the texts into tokens with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+/// * `max_skip` - The maximum skip distance, e.g. `Some(4)` for ROUGE-SU4. `None` means there is no limit.
+/// * `tokenizer` - The tokenizer splitting both texts into tokens.
+///
+/// ### Returns
+///
+/// A `Score` struct containing precision, recall, and F1 score based on skip-bigrams and unigrams.
+pub fn rouge_su_with_tokenizer(input: &str, reference: &str, max_skip: Option<usize>, tokenizer: &dyn Tokenizer) -> Score {
+    let input_tokens = tokenizer.tokenize(input);
+    let reference_tokens = tokenizer.tokenize(reference);
+    let input_words: Vec<&str> = as_strs(&input_tokens);
+    let reference_words: Vec<&str> = as_strs(&reference_tokens);
+
+    let mut input_grams = create_skip_bigrams(input_words.clone(), max_skip);
+    input_grams.extend(create_ngrams(input_words, 1));
+    let mut reference_grams = create_skip_bigrams(reference_words.clone(), max_skip);
+    reference_grams.extend(create_ngrams(reference_words, 1));
+
+    ngram_based_score(input_grams, reference_grams, 1.0)
+}
+
+/// The default weighting exponent of ROUGE-W, as used in Lin (2004).
+pub const DEFAULT_ROUGE_W_WEIGHT: f32 = 1.2;
+
+/// Computes the weighted longest common subsequence (WLCS) of two token sequences.
+///
+/// WLCS works like the LCS, but it remembers the length of the consecutive match ending at each cell,
+/// and a match of length `k` is worth `k^weight`. With `weight > 1`, consecutive matches
+/// are rewarded more than the same number of scattered matches.
+///
+/// ### Arguments
+///
+/// * `a` - A slice of string slices representing the first token sequence.
+/// * `b` - A slice of string slices representing the second token sequence.
+/// * `weight` - The weighting exponent of `f(k) = k^weight`.
+///
+/// ### Returns
+///
+/// The WLCS score of `a` and `b`.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::weighted_lcs;
+///
+/// let x = vec!["A", "B", "C", "D", "E", "F", "G"];
+///
+/// assert_eq!(16.0, weighted_lcs(&x, &["A", "B", "C", "D", "H", "I", "K"], 2.0));
+/// assert_eq!(4.0, weighted_lcs(&x, &["A", "H", "B", "K", "C", "I", "D"], 2.0));
+/// ```
+pub fn weighted_lcs(a: &[&str], b: &[&str], weight: f32) -> f32 {
+    let f = |k: u32| (k as f32).powf(weight);
+    let mut scores: Vec<Vec<f32>> = vec![vec![0.0; b.len() + 1]; a.len() + 1];
+    let mut consecutive: Vec<Vec<u32>> = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            if a[i - 1] == b[j - 1] {
+                let k = consecutive[i - 1][j - 1];
+                scores[i][j] = scores[i - 1][j - 1] + f(k + 1) - f(k);
+                consecutive[i][j] = k + 1;
+            } else {
+                scores[i][j] = scores[i - 1][j].max(scores[i][j - 1]);
+            }
+        }
+    }
+    scores[a.len()][b.len()]
+}
+
+/// Computes ROUGE-W scores based on the weighted longest common subsequence for a given input and reference text.
+///
+/// ROUGE-W is a variant of ROUGE-L that favors consecutive matches: for the same LCS length,
+/// an input that matches the reference in one block scores higher than one whose matches are scattered.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+/// * `weight` - The weighting exponent, `DEFAULT_ROUGE_W_WEIGHT` (1.2) is commonly used.
+///
+/// ### Returns
+///
+/// A `Result` containing a `Score` struct if successful, or an error message if `weight` is less than 1 or NaN.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::{rouge_w, DEFAULT_ROUGE_W_WEIGHT};
+///
+/// let reference = "A B C D E F G";
+///
+/// let consecutive = rouge_w("A B C D H I K", reference, DEFAULT_ROUGE_W_WEIGHT).unwrap();
+/// let scattered = rouge_w("A H B K C I D", reference, DEFAULT_ROUGE_W_WEIGHT).unwrap();
+/// assert!(consecutive.f1 > scattered.f1);
+/// ```
+///
+/// # Note
+///
+/// - Precision is `f^-1(WLCS / f(len(input)))` and recall is `f^-1(WLCS / f(len(reference)))`,
+///   where `f(k) = k^weight`.
+/// - With `weight` of 1, ROUGE-W is the same as `rouge_l`.
+pub fn rouge_w(input: &str, reference: &str, weight: f32) -> Result<Score> {
+    rouge_w_with_tokenizer(input, reference, weight, &WhitespaceTokenizer)
+}
+
+/// Computes ROUGE-W scores, splitting the texts into tokens with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `reference` - The reference text, considered as the ground truth or gold standard.
+/// * `weight` - The weighting exponent, `DEFAULT_ROUGE_W_WEIGHT` (1.2) is commonly used.
+/// * `tokenizer` - The tokenizer splitting both texts into tokens.
+///
+/// ### Returns
+///
+/// A `Result` containing a `Score` struct if successful, or an error message if `weight` is less than 1 or NaN.
+pub fn rouge_w_with_tokenizer(input: &str, reference: &str, weight: f32, tokenizer: &dyn Tokenizer) -> Result<Score> {
+    if weight.is_nan() || weight < 1.0 {
+        return Err(MetricError::InvalidWeight(weight));
+    }
+
+    let input_tokens = tokenizer.tokenize(input);
+    let reference_tokens = tokenizer.tokenize(reference);
+    let input_words: Vec<&str> = as_strs(&input_tokens);
+    let reference_words: Vec<&str> = as_strs(&reference_tokens);
+
+    let wlcs = weighted_lcs(&input_words, &reference_words, weight);
+    let f = |k: usize| (max(k, 1) as f32).powf(weight);
+    let f_inv = |x: f32| x.powf(1.0 / weight);
+
+    let p: f32 = f_inv(wlcs / f(input_words.len()));
+    let r: f32 = f_inv(wlcs / f(reference_words.len()));
+    let f: f32 = f1(p, r);
+
+    Ok(Score{precision:p, recall:r, f1:f, beta:1.0})
+}
+
+/// Specifies how the scores against multiple references are combined into one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Takes the score of the reference with the highest F1, as `rouge_score` does.
+    Max,
+    /// Averages precision, recall and F1 over all references.
+    Average,
+}
+
+/// Represents the aggregated score of an input against multiple references.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MultiReferenceScore {
+    /// The aggregated score.
+    pub score: Score,
+    /// The index of the reference with the highest F1. The first one wins ties.
+    pub best_reference: usize,
+}
+
+/// Computes a score of an input text against multiple reference texts.
+///
+/// The input is scored against every reference with the given `scorer`,
+/// and the scores are combined according to `aggregation`.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `references` - The reference texts, considered as the ground truth or gold standard.
+/// * `aggregation` - How to combine the scores against each reference.
+/// * `scorer` - A function computing the score of an input against a single reference, e.g. `rouge_n`.
+///
+/// ### Returns
+///
+/// A `Result` containing a `MultiReferenceScore` if successful, or an error message if `references` is empty
+/// or the `scorer` fails.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::{multi_reference_score, rouge_l, rouge_n, Aggregation};
+///
+/// let references = ["the cat sat on the mat", "a cat was sitting on the mat"];
+///
+/// let result = multi_reference_score("the cat was on the mat", &references, Aggregation::Max, |i, r| rouge_n(i, r, 2)).unwrap();
+/// println!("F1 Score: {}, from reference {}", result.score.f1, result.best_reference);
+///
+/// let result = multi_reference_score("the cat was on the mat", &references, Aggregation::Average, |i, r| Ok(rouge_l(i, r))).unwrap();
+/// println!("F1 Score: {}", result.score.f1);
+/// ```
+pub fn multi_reference_score<F>(input: &str, references: &[&str], aggregation: Aggregation, scorer: F) -> Result<MultiReferenceScore>
+where
+    F: Fn(&str, &str) -> Result<Score>,
+{
+    if references.is_empty() {
+        return Err(MetricError::EmptyInput("references"));
+    }
+
+    let scores: Vec<Score> = references
+        .iter()
+        .map(|reference| scorer(input, reference))
+        .collect::<Result<Vec<Score>>>()?;
+
+    let mut best_reference: usize = 0;
+    for (i, score) in scores.iter().enumerate() {
+        if score.f1 > scores[best_reference].f1 {
+            best_reference = i;
+        }
+    }
+
+    let score = match aggregation {
+        Aggregation::Max => scores[best_reference],
+        Aggregation::Average => {
+            let count = scores.len() as f32;
+            Score{
+                precision: scores.iter().map(|s| s.precision).sum::<f32>() / count,
+                recall: scores.iter().map(|s| s.recall).sum::<f32>() / count,
+                f1: scores.iter().map(|s| s.f1).sum::<f32>() / count,
+                beta: scores[0].beta,
+            }
+        }
+    };
+
+    Ok(MultiReferenceScore{score, best_reference})
+}
+
+/// The types of ROUGE scores computed by `RougeScorer`, named as in `rouge_score`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RougeType {
+    /// ROUGE-N, named `rouge1` to `rouge9`.
+    RougeN(usize),
+    /// Sentence-level ROUGE-L, named `rougeL`.
+    RougeL,
+    /// Summary-level ROUGE-L, named `rougeLsum`.
+    RougeLsum,
+}
+
+impl FromStr for RougeType {
+    type Err = MetricError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "rougeL" => Ok(RougeType::RougeL),
+            "rougeLsum" => Ok(RougeType::RougeLsum),
+            _ => match name.strip_prefix("rouge").and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) if (1..=9).contains(&n) && name.len() == 6 => Ok(RougeType::RougeN(n)),
+                _ => Err(MetricError::InvalidRougeType(name.to_string())),
+            },
+        }
+    }
+}
+
+impl fmt::Display for RougeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RougeType::RougeN(n) => write!(f, "rouge{}", n),
+            RougeType::RougeL => write!(f, "rougeL"),
+            RougeType::RougeLsum => write!(f, "rougeLsum"),
+        }
+    }
+}
+
+/// Computes several types of ROUGE scores at once, mirroring `rouge_scorer.RougeScorer` of `rouge_score`.
+///
+/// The texts are tokenized once and every requested score is computed from the same tokens.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::RougeScorer;
+/// use text_score::stemmer::NltkPorterStemmer;
+///
+/// // rouge_scorer.RougeScorer(["rouge1", "rougeL"], use_stemmer=True)
+/// let scorer = RougeScorer::new(&["rouge1", "rougeL"]).unwrap().with_stemmer(NltkPorterStemmer);
+///
+/// let scores = scorer.score("The quick brown fox jumps over the lazy dog", "The quick brown dog jumps on the log.");
+/// println!("rouge1: {}", scores["rouge1"].f1);
+/// println!("rougeL: {}", scores["rougeL"].f1);
+/// ```
+pub struct RougeScorer {
+    rouge_types: Vec<RougeType>,
+    stemmer: Option<Box<dyn Stemmer>>,
+    tokenizer: Box<dyn Tokenizer>,
+}
+
+impl RougeScorer {
+    /// Creates a scorer for the given rouge types, with the tokenizer of `rouge_score` and no stemming.
+    ///
+    /// ### Arguments
+    ///
+    /// * `rouge_types` - The names of the scores, `rouge1` to `rouge9`, `rougeL` or `rougeLsum`.
+    ///
+    /// ### Returns
+    ///
+    /// A `Result` containing the scorer if successful, or an error message if a rouge type is unknown.
+    pub fn new(rouge_types: &[&str]) -> Result<Self> {
+        let rouge_types = rouge_types.iter().map(|name| name.parse()).collect::<Result<Vec<RougeType>>>()?;
+        Ok(RougeScorer{rouge_types, stemmer: None, tokenizer: Box::new(RougeTokenizer)})
+    }
+
+    /// Stems the tokens longer than `ROUGE_MIN_STEM_LEN` characters, as `use_stemmer=True` of `rouge_score`
+    /// with `NltkPorterStemmer`.
+    pub fn with_stemmer<S: Stemmer + 'static>(mut self, stemmer: S) -> Self {
+        self.stemmer = Some(Box::new(stemmer));
+        self
+    }
+
+    /// Splits the texts into tokens with the given tokenizer instead of `RougeTokenizer`.
+    /// The tokens are still stemmed if a stemmer is set.
+    pub fn with_tokenizer<T: Tokenizer + 'static>(mut self, tokenizer: T) -> Self {
+        self.tokenizer = Box::new(tokenizer);
+        self
+    }
+
+    /// Returns the rouge types computed by the scorer.
+    pub fn rouge_types(&self) -> &[RougeType] {
+        &self.rouge_types
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        let tokens = self.tokenizer.tokenize(text);
+        match &self.stemmer {
+            Some(stemmer) => tokens
+                .into_iter()
+                .map(|token| if token.chars().count() > ROUGE_MIN_STEM_LEN { stemmer.stem(&token) } else { token })
+                .collect(),
+            None => tokens,
+        }
+    }
+
+    /// Computes the scores of a prediction against a target.
+    ///
+    /// ### Arguments
+    ///
+    /// * `target` - The target text, considered as the ground truth or gold standard.
+    /// * `prediction` - The predicted text to be evaluated.
+    ///
+    /// ### Returns
+    ///
+    /// A `HashMap` from rouge type name, e.g. `rouge1`, to its `Score`.
+    ///
+    /// # Note
+    ///
+    /// - The arguments are in the order of `rouge_score`, target first, unlike the functions of this module.
+    /// - For `rougeLsum`, the texts are split into sentences on newlines, see `rouge_lsum`.
+    pub fn score(&self, target: &str, prediction: &str) -> HashMap<String, Score> {
+        let needs_tokens = self.rouge_types.iter().any(|t| *t != RougeType::RougeLsum);
+        let (target_tokens, prediction_tokens) = if needs_tokens {
+            (self.tokenize(target), self.tokenize(prediction))
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        let target_words = as_strs(&target_tokens);
+        let prediction_words = as_strs(&prediction_tokens);
+
+        let mut scores: HashMap<String, Score> = HashMap::new();
+        for rouge_type in self.rouge_types.iter() {
+            let score = match rouge_type {
+                RougeType::RougeN(n) => ngram_based_score(
+                    create_ngrams(prediction_words.clone(), *n),
+                    create_ngrams(target_words.clone(), *n),
+                    1.0,
+                ),
+                RougeType::RougeL => lcs_score(&prediction_words, &target_words),
+                RougeType::RougeLsum => {
+                    let sentences = |text: &str| -> Vec<Vec<String>> {
+                        text.lines().map(|line| self.tokenize(line)).filter(|tokens| !tokens.is_empty()).collect()
+                    };
+                    let target_sentences = sentences(target);
+                    let prediction_sentences = sentences(prediction);
+                    summary_lcs_score(
+                        &prediction_sentences.iter().map(|s| as_strs(s)).collect::<Vec<Vec<&str>>>(),
+                        &target_sentences.iter().map(|s| as_strs(s)).collect::<Vec<Vec<&str>>>(),
+                    )
+                }
+            };
+            scores.insert(rouge_type.to_string(), score);
+        }
+        scores
+    }
 }
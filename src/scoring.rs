@@ -0,0 +1,154 @@
+//! Aggregation of per-example scores into corpus-level scores.
+//!
+//! `BootstrapAggregator` follows `scoring.BootstrapAggregator` of the google research rouge implementation:
+//! the mean of the scores is reported together with a confidence interval estimated by bootstrap resampling.
+//!
+use std::collections::HashMap;
+use crate::commons::{MetricError, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::commons::Score;
+
+/// Represents a corpus-level score with its bootstrap confidence interval.
+///
+/// `low`, `mid` and `high` are percentiles of the resampled means, e.g. 2.5th, 50th and 97.5th
+/// for a 95% confidence interval, and `mean` is the plain mean over all scores.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AggregateScore {
+    /// The mean of all the scores, without resampling.
+    pub mean: Score,
+    /// The lower bound of the confidence interval at the configured `confidence_interval`.
+    pub low: Score,
+    /// The median of the resampled means.
+    pub mid: Score,
+    /// The upper bound of the confidence interval at the configured `confidence_interval`.
+    pub high: Score,
+}
+
+/// Aggregates scores of many examples into corpus-level scores per metric.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::rouge::{rouge_l, rouge_n};
+/// use text_score::scoring::BootstrapAggregator;
+///
+/// let mut aggregator = BootstrapAggregator::new(1000, 0.95, 42).unwrap();
+///
+/// for (input, reference) in [("the cat sat", "the cat sat down"), ("a dog ran", "the dog ran away")] {
+///     aggregator.add_score("rouge1", rouge_n(input, reference, 1).unwrap());
+///     aggregator.add_score("rougeL", rouge_l(input, reference));
+/// }
+///
+/// let result = aggregator.aggregate();
+/// let rouge1 = result["rouge1"];
+/// println!("rouge1 F1: {} ({} - {})", rouge1.mean.f1, rouge1.low.f1, rouge1.high.f1);
+/// ```
+///
+/// # Note
+///
+/// - Resampling of each metric uses its own RNG seeded with `seed`, so the result is reproducible
+///   and does not depend on which other metrics are aggregated.
+pub struct BootstrapAggregator {
+    n_samples: usize,
+    confidence_interval: f32,
+    seed: u64,
+    scores: HashMap<String, Vec<Score>>,
+}
+
+impl BootstrapAggregator {
+    /// Creates a new aggregator.
+    ///
+    /// ### Arguments
+    ///
+    /// * `n_samples` - The number of bootstrap samples, `rouge_score` uses 1000.
+    /// * `confidence_interval` - The confidence level of the interval, between 0 and 1 (exclusive), e.g. 0.95.
+    /// * `seed` - The seed of the RNG used for resampling.
+    ///
+    /// ### Returns
+    ///
+    /// A `Result` containing the aggregator if successful, or an error message if `n_samples` is 0
+    /// or `confidence_interval` is out of range.
+    pub fn new(n_samples: usize, confidence_interval: f32, seed: u64) -> Result<Self> {
+        if n_samples < 1 {
+            return Err(MetricError::InvalidCount("n_samples"));
+        }
+        if !(confidence_interval > 0.0 && confidence_interval < 1.0) {
+            return Err(MetricError::InvalidProbability{name: "confidence_interval", value: confidence_interval});
+        }
+
+        Ok(BootstrapAggregator{n_samples, confidence_interval, seed, scores: HashMap::new()})
+    }
+
+    /// Adds the score of a single example for the given metric.
+    pub fn add_score(&mut self, metric: &str, score: Score) {
+        self.scores.entry(metric.to_string()).or_default().push(score);
+    }
+
+    /// Adds the scores of a single example for several metrics at once, e.g. keyed by rouge type.
+    pub fn add_scores(&mut self, scores: &HashMap<String, Score>) {
+        for (metric, score) in scores.iter() {
+            self.add_score(metric, *score);
+        }
+    }
+
+    /// Computes the mean and the bootstrap confidence interval of every metric added so far.
+    ///
+    /// ### Returns
+    ///
+    /// A `HashMap` from metric name to its `AggregateScore`.
+    pub fn aggregate(&self) -> HashMap<String, AggregateScore> {
+        let lower = (1.0 - self.confidence_interval) / 2.0;
+        let percentiles = [lower, 0.5, 1.0 - lower];
+
+        self.scores
+            .iter()
+            .map(|(metric, scores)| {
+                let mut rng = StdRng::seed_from_u64(self.seed);
+
+                // means of every bootstrap sample, one vector per field
+                let mut precisions: Vec<f32> = Vec::with_capacity(self.n_samples);
+                let mut recalls: Vec<f32> = Vec::with_capacity(self.n_samples);
+                let mut f1s: Vec<f32> = Vec::with_capacity(self.n_samples);
+                for _ in 0..self.n_samples {
+                    let sample: Vec<Score> = (0..scores.len()).map(|_| scores[rng.gen_range(0..scores.len())]).collect();
+                    let sample_mean = mean(&sample);
+                    precisions.push(sample_mean.precision);
+                    recalls.push(sample_mean.recall);
+                    f1s.push(sample_mean.f1);
+                }
+                precisions.sort_by(f32::total_cmp);
+                recalls.sort_by(f32::total_cmp);
+                f1s.sort_by(f32::total_cmp);
+
+                let [low, mid, high] = percentiles.map(|q| Score{
+                    precision: percentile(&precisions, q),
+                    recall: percentile(&recalls, q),
+                    f1: percentile(&f1s, q),
+                    beta: scores[0].beta,
+                });
+
+                (metric.clone(), AggregateScore{mean: mean(scores), low, mid, high})
+            })
+            .collect()
+    }
+}
+
+/// Computes the field-wise mean of non-empty scores, with the beta of the first one.
+fn mean(scores: &[Score]) -> Score {
+    let count = scores.len() as f32;
+    Score{
+        precision: scores.iter().map(|s| s.precision).sum::<f32>() / count,
+        recall: scores.iter().map(|s| s.recall).sum::<f32>() / count,
+        f1: scores.iter().map(|s| s.f1).sum::<f32>() / count,
+        beta: scores[0].beta,
+    }
+}
+
+/// Computes the `q`-th quantile of non-empty sorted values with linear interpolation, as `numpy.percentile` does.
+fn percentile(sorted: &[f32], q: f32) -> f32 {
+    let position = q * (sorted.len() - 1) as f32;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f32)
+}
@@ -0,0 +1,337 @@
+//! Entity-level metrics of sequence labeling, e.g. named entity recognition, compatible with seqeval.
+//!
+//! Tags are decoded into entities, i.e. spans of tokens with a type, and an entity is counted as correct only if
+//! the same span with the same type is in the reference. By default, tags are decoded leniently as conlleval does,
+//! whatever the scheme. In strict mode, the tags have to follow the given scheme: tags not allowed by it are errors,
+//! and chunks which do not follow it, e.g. an `I-PER` not preceded by `B-PER` in BIO, are not entities.
+//!
+use std::fmt;
+use std::cmp::max;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use crate::classification::{counts_score, weighted_mean, ClassCounts, ClassReport};
+use crate::commons::{MetricError, Result, Score, ZeroDivision};
+
+/// The tagging schemes, named by their prefixes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// `I-` inside chunks, `B-` only for a chunk following a chunk of the same type.
+    Iob1,
+    /// Also known as IOB2: `B-` at the beginning of every chunk, `I-` inside.
+    Bio,
+    /// `I-` inside chunks, `E-` only for a chunk followed by a chunk of the same type.
+    Ioe1,
+    /// `E-` at the end of every chunk, `I-` inside.
+    Ioe2,
+    /// Also known as IOBES: `B-` begin, `I-` inside, `E-` end of chunks, and `S-` for single token chunks.
+    Bioes,
+}
+
+impl Scheme {
+    fn allows(self, prefix: char) -> bool {
+        let allowed = match self {
+            Scheme::Iob1 | Scheme::Bio => "BIO",
+            Scheme::Ioe1 | Scheme::Ioe2 => "IEO",
+            Scheme::Bioes => "BIESO",
+        };
+        allowed.contains(prefix)
+    }
+
+    /// Returns `true` if `token` begins a chunk after `prev`.
+    fn is_start(self, prev: Tag, token: Tag) -> bool {
+        let other_type = prev.prefix == 'O' || prev.entity_type != token.entity_type;
+        match self {
+            Scheme::Iob1 => match token.prefix {
+                'I' => other_type,
+                'B' => !other_type,
+                _ => false,
+            },
+            Scheme::Bio => token.prefix == 'B',
+            Scheme::Ioe1 | Scheme::Ioe2 => matches!(token.prefix, 'I' | 'E') && (other_type || prev.prefix == 'E'),
+            Scheme::Bioes => matches!(token.prefix, 'B' | 'S'),
+        }
+    }
+
+    /// Returns `true` if `token` continues the chunk whose last token is `last`.
+    fn is_inside(self, last: Tag, token: Tag) -> bool {
+        if last.entity_type != token.entity_type {
+            return false;
+        }
+        match self {
+            Scheme::Iob1 | Scheme::Bio => token.prefix == 'I',
+            Scheme::Ioe1 | Scheme::Ioe2 => last.prefix == 'I' && matches!(token.prefix, 'I' | 'E'),
+            Scheme::Bioes => matches!(last.prefix, 'B' | 'I') && matches!(token.prefix, 'I' | 'E'),
+        }
+    }
+
+    /// Returns `true` if a chunk whose last token is `last` can end before `next`.
+    fn is_end(self, last: Tag, next: Tag) -> bool {
+        match self {
+            Scheme::Iob1 | Scheme::Bio => true,
+            Scheme::Ioe1 => last.prefix == 'I' || (next.prefix != 'O' && next.entity_type == last.entity_type),
+            Scheme::Ioe2 => last.prefix == 'E',
+            Scheme::Bioes => matches!(last.prefix, 'E' | 'S'),
+        }
+    }
+}
+
+/// Specifies how tags are decoded into entities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Decodes any scheme as conlleval does, e.g. an `I-PER` after `O` begins an entity. The default of seqeval.
+    #[default]
+    Lenient,
+    /// Decodes the tags following the scheme, as seqeval with `mode="strict"`.
+    Strict(Scheme),
+}
+
+/// Represents an entity, i.e. a span of tokens of a sentence with a type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    /// The index of the sentence.
+    pub sentence: usize,
+    /// The index of the first token.
+    pub start: usize,
+    /// The index after the last token.
+    pub end: usize,
+    /// The type, e.g. `PER` for `B-PER`.
+    pub entity_type: String,
+}
+
+/// A tag split into its prefix and its type. `O` has the prefix `O` and no type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Tag<'a> {
+    prefix: char,
+    entity_type: &'a str,
+}
+
+const OUTSIDE: Tag<'static> = Tag{prefix: 'O', entity_type: ""};
+
+impl<'a> Tag<'a> {
+    /// Splits a tag as seqeval does: the first character is the prefix, and the type follows the first `-`.
+    fn parse(tag: &'a str) -> Tag<'a> {
+        let mut chars = tag.chars();
+        let prefix = chars.next().unwrap_or('O');
+        let rest = chars.as_str();
+        let entity_type = rest.split_once('-').map_or(rest, |(_, entity_type)| entity_type);
+        Tag{prefix, entity_type}
+    }
+
+    /// Splits a tag which has to be `O` or a prefix allowed by `scheme`, a `-` and a type.
+    fn parse_strict(tag: &'a str, scheme: Scheme) -> Result<Tag<'a>> {
+        if tag == "O" {
+            return Ok(OUTSIDE);
+        }
+        match tag.split_once('-') {
+            Some((prefix, entity_type)) if prefix.len() == 1 && !entity_type.is_empty() => {
+                let prefix = prefix.chars().next().unwrap_or('O');
+                if prefix != 'O' && scheme.allows(prefix) {
+                    return Ok(Tag{prefix, entity_type});
+                }
+                Err(MetricError::InvalidTag(tag.to_string()))
+            }
+            _ => Err(MetricError::InvalidTag(tag.to_string())),
+        }
+    }
+}
+
+/// Returns `true` if a chunk ends between `prev` and `tag`, following `end_of_chunk` of conlleval.
+fn end_of_chunk(prev: Tag, tag: Tag) -> bool {
+    matches!(prev.prefix, 'E' | 'S')
+        || (matches!(prev.prefix, 'B' | 'I') && matches!(tag.prefix, 'B' | 'S' | 'O'))
+        || (!matches!(prev.prefix, 'O' | '.') && prev.entity_type != tag.entity_type)
+}
+
+/// Returns `true` if a chunk starts at `tag` after `prev`, following `start_of_chunk` of conlleval.
+fn start_of_chunk(prev: Tag, tag: Tag) -> bool {
+    matches!(tag.prefix, 'B' | 'S')
+        || (matches!(prev.prefix, 'E' | 'S' | 'O') && matches!(tag.prefix, 'E' | 'I'))
+        || (!matches!(tag.prefix, 'O' | '.') && prev.entity_type != tag.entity_type)
+}
+
+/// Decodes the tags of a sentence into entities.
+///
+/// ### Arguments
+///
+/// * `tags` - The tag of each token of the sentence, e.g. `["B-PER", "I-PER", "O"]`.
+/// * `mode` - Whether the tags are decoded leniently or following a scheme.
+///
+/// ### Returns
+///
+/// A `Result` containing the entities in order, with `sentence` 0, or `MetricError::InvalidTag`
+/// if a tag is not allowed by the scheme in strict mode.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::sequence_labeling::{get_entities, Mode, Scheme};
+///
+/// let tags = ["I-PER", "I-PER", "O", "B-LOC"];
+///
+/// // an entity may begin with I- in lenient mode, but not in strict BIO
+/// assert_eq!(2, get_entities(&tags, Mode::Lenient).unwrap().len());
+/// let entities = get_entities(&tags, Mode::Strict(Scheme::Bio)).unwrap();
+/// assert_eq!((3, 4, "LOC"), (entities[0].start, entities[0].end, entities[0].entity_type.as_str()));
+/// ```
+pub fn get_entities(tags: &[&str], mode: Mode) -> Result<Vec<Entity>> {
+    let mut spans: Vec<(usize, usize, &str)> = Vec::new();
+    match mode {
+        Mode::Lenient => {
+            let parsed: Vec<Tag> = tags.iter().map(|tag| Tag::parse(tag)).collect();
+            let (mut prev, mut begin) = (OUTSIDE, 0);
+            for (i, &tag) in parsed.iter().chain([OUTSIDE].iter()).enumerate() {
+                // conlleval gives `O` the type `_`, and other tags without type as well
+                let tag = if tag.entity_type.is_empty() { Tag{entity_type: "_", ..tag} } else { tag };
+                if i > 0 && end_of_chunk(prev, tag) {
+                    spans.push((begin, i, prev.entity_type));
+                }
+                if start_of_chunk(prev, tag) {
+                    begin = i;
+                }
+                prev = tag;
+            }
+        }
+        Mode::Strict(scheme) => {
+            let parsed: Vec<Tag> = tags.iter().map(|tag| Tag::parse_strict(tag, scheme)).collect::<Result<Vec<Tag>>>()?;
+            let token = |i: usize| if i < parsed.len() { parsed[i] } else { OUTSIDE };
+            let mut i = 0;
+            while i < parsed.len() {
+                let prev = if i == 0 { OUTSIDE } else { parsed[i - 1] };
+                if !scheme.is_start(prev, parsed[i]) {
+                    i += 1;
+                    continue;
+                }
+                let mut end = i + 1;
+                while end < parsed.len() && scheme.is_inside(parsed[end - 1], parsed[end]) {
+                    end += 1;
+                }
+                if scheme.is_end(parsed[end - 1], token(end)) {
+                    spans.push((i, end, parsed[i].entity_type));
+                }
+                i = end;
+            }
+        }
+    }
+    Ok(spans
+        .into_iter()
+        .map(|(start, end, entity_type)| Entity{sentence: 0, start, end, entity_type: entity_type.to_string()})
+        .collect())
+}
+
+/// Represents the entity-level scores of sequence labeling, per entity type and averaged,
+/// as `classification_report` of seqeval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityReport {
+    /// The score of each entity type, sorted by type. The support is the number of reference entities.
+    pub types: Vec<ClassReport<String>>,
+    /// The score of the correct, predicted and reference entities of all types.
+    pub micro_avg: Score,
+    /// The unweighted mean of the scores of the entity types.
+    pub macro_avg: Score,
+    /// The mean of the scores of the entity types weighted by their support.
+    pub weighted_avg: Score,
+    /// The number of reference entities.
+    pub support: u32,
+}
+
+impl EntityReport {
+    /// Returns the score and support of an entity type, if it appears in the references or the predictions.
+    pub fn entity_type(&self, entity_type: &str) -> Option<&ClassReport<String>> {
+        self.types.iter().find(|c| c.label == entity_type)
+    }
+}
+
+impl fmt::Display for EntityReport {
+    /// Formats the report as seqeval does, with the precision of the formatter as the number of digits (2 by default).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = f.precision().unwrap_or(2);
+        let width = self.types.iter().map(|c| c.label.chars().count()).chain(["weighted avg".len()]).max().unwrap_or(0);
+        let column = max(digits + 2, "precision".len());
+
+        writeln!(f, "{:>width$}  {:>column$} {:>column$} {:>column$} {:>column$}", "", "precision", "recall", "f1-score", "support")?;
+        for class in self.types.iter() {
+            let s = class.score;
+            write!(f, "\n{:>width$}  {:>column$.digits$} {:>column$.digits$} {:>column$.digits$} {:>column$}", class.label, s.precision, s.recall, s.f1, class.support)?;
+        }
+        writeln!(f)?;
+        for (name, s) in [("micro avg", self.micro_avg), ("macro avg", self.macro_avg), ("weighted avg", self.weighted_avg)] {
+            write!(f, "\n{:>width$}  {:>column$.digits$} {:>column$.digits$} {:>column$.digits$} {:>column$}", name, s.precision, s.recall, s.f1, self.support)?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the entity-level scores of predicted tags against reference tags.
+///
+/// ### Arguments
+///
+/// * `y_true` - The reference tags of each sentence.
+/// * `y_pred` - The predicted tags of each sentence.
+/// * `mode` - Whether the tags are decoded leniently, the default of seqeval, or following a scheme.
+/// * `zero_division` - The policy for the precision of a type never predicted and the recall of a type never
+///   in the references.
+///
+/// ### Returns
+///
+/// A `Result` containing the report if successful, `MetricError::LengthMismatch` if the numbers of sentences or
+/// of tokens of a sentence differ, `MetricError::InvalidTag` if a tag is not allowed by the scheme in strict mode,
+/// or `MetricError::ZeroDivision` if the policy is `ZeroDivision::Error` and a division by zero occurs.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::commons::ZeroDivision;
+/// use text_score::sequence_labeling::{entity_report, Mode};
+///
+/// let y_true = vec![vec!["O", "O", "B-MISC", "I-MISC", "I-MISC", "O"], vec!["B-PER", "I-PER", "O"]];
+/// let y_pred = vec![vec!["O", "O", "B-MISC", "I-MISC", "I-MISC", "O"], vec!["B-PER", "I-PER", "O"]];
+///
+/// let report = entity_report(&y_true, &y_pred, Mode::default(), ZeroDivision::Zero).unwrap();
+/// assert_eq!(1.0, report.micro_avg.f1);
+/// println!("{}", report);
+/// ```
+pub fn entity_report(y_true: &[Vec<&str>], y_pred: &[Vec<&str>], mode: Mode, zero_division: ZeroDivision) -> Result<EntityReport> {
+    if y_true.len() != y_pred.len() {
+        return Err(MetricError::LengthMismatch{inputs: y_pred.len(), references: y_true.len()});
+    }
+    let mut true_entities: HashSet<Entity> = HashSet::new();
+    let mut pred_entities: HashSet<Entity> = HashSet::new();
+    for (sentence, (true_tags, pred_tags)) in y_true.iter().zip(y_pred.iter()).enumerate() {
+        if true_tags.len() != pred_tags.len() {
+            return Err(MetricError::LengthMismatch{inputs: pred_tags.len(), references: true_tags.len()});
+        }
+        true_entities.extend(get_entities(true_tags, mode)?.into_iter().map(|e| Entity{sentence, ..e}));
+        pred_entities.extend(get_entities(pred_tags, mode)?.into_iter().map(|e| Entity{sentence, ..e}));
+    }
+
+    let mut counts: HashMap<&str, ClassCounts> = HashMap::new();
+    for entity in true_entities.iter() {
+        let type_counts = counts.entry(&entity.entity_type).or_default();
+        if pred_entities.contains(entity) {
+            type_counts.true_pos += 1;
+        } else {
+            type_counts.false_neg += 1;
+        }
+    }
+    for entity in pred_entities.difference(&true_entities) {
+        counts.entry(&entity.entity_type).or_default().false_pos += 1;
+    }
+
+    let mut total = ClassCounts::default();
+    let mut types: Vec<ClassReport<String>> = Vec::new();
+    for entity_type in counts.keys().collect::<BTreeSet<&&str>>() {
+        let type_counts = counts[*entity_type];
+        total.true_pos += type_counts.true_pos;
+        total.false_pos += type_counts.false_pos;
+        total.false_neg += type_counts.false_neg;
+        types.push(ClassReport{label: entity_type.to_string(), score: counts_score(type_counts, zero_division)?, support: type_counts.support()});
+    }
+
+    let scores: Vec<Score> = types.iter().map(|c| c.score).collect();
+    Ok(EntityReport{
+        micro_avg: counts_score(total, zero_division)?,
+        macro_avg: weighted_mean(&scores, &vec![1.0; scores.len()], zero_division)?,
+        weighted_avg: weighted_mean(&scores, &types.iter().map(|c| c.support as f32).collect::<Vec<f32>>(), zero_division)?,
+        support: total.support(),
+        types,
+    })
+}
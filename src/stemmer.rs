@@ -0,0 +1,361 @@
+//! Stemmers reducing inflected words to a common stem, so that e.g. "runs" and "running" can be matched.
+//!
+//! The Porter stemmer is built in, both as in Martin Porter's reference implementation and with the extensions
+//! of NLTK used by `rouge_score`, and any `Fn(&str) -> String` can be used as a `Stemmer`,
+//! so an external stemmer is easily plugged in.
+//!
+
+/// Reduces a word to its stem.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::stemmer::Stemmer;
+///
+/// let strip_s = |word: &str| word.trim_end_matches('s').to_string();
+/// assert_eq!("cat", strip_s.stem("cats"));
+/// ```
+pub trait Stemmer {
+    /// Returns the stem of `word`.
+    fn stem(&self, word: &str) -> String;
+}
+
+impl<F> Stemmer for F
+where
+    F: Fn(&str) -> String,
+{
+    fn stem(&self, word: &str) -> String {
+        self(word)
+    }
+}
+
+/// The Porter stemmer (Porter, 1980), as in the reference implementation by Martin Porter.
+///
+/// The reference implementation departs from the paper in three places, which are kept here:
+/// "bli" is replaced by "ble" instead of "abli" by "able", "logi" is replaced by "log",
+/// and words of at most two letters are not stemmed.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::stemmer::{PorterStemmer, Stemmer};
+///
+/// assert_eq!("run", PorterStemmer.stem("running"));
+/// assert_eq!("run", PorterStemmer.stem("runs"));
+/// assert_eq!("gener", PorterStemmer.stem("generalizations"));
+/// ```
+///
+/// # Note
+///
+/// - Words are expected to be lowercase, as produced by `RougeTokenizer`. Letters other than `a-z` are treated as consonants.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PorterStemmer;
+
+impl Stemmer for PorterStemmer {
+    fn stem(&self, word: &str) -> String {
+        porter_stem(word, false)
+    }
+}
+
+/// The Porter stemmer in the `NLTK_EXTENSIONS` mode of NLTK, which is the stemmer of `rouge_score`.
+///
+/// The extensions depart from `PorterStemmer` in a few places:
+///
+/// - irregular forms are looked up first, e.g. "dying" -> "die", "lying" -> "lie" and "skies" -> "sky",
+/// - "-ies" and "-ied" give "-ie" in four letter words, e.g. "dies" -> "die", and "-ied" gives "-i" otherwise,
+/// - a final "y" is turned into "i" only after a consonant, e.g. "enjoy" is kept,
+/// - "-alli" is replaced by "-al" before the other suffixes of step 2, "-fulli" by "-ful", and "logi" by "log"
+///   when the stem including the "l" has a positive measure,
+/// - two letter stems of a vowel and a consonant end with consonant-vowel-consonant, e.g. "using" -> "use".
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::stemmer::{NltkPorterStemmer, PorterStemmer, Stemmer};
+///
+/// assert_eq!("run", NltkPorterStemmer.stem("running"));
+/// assert_eq!("die", NltkPorterStemmer.stem("dying"));
+/// assert_eq!("dy", PorterStemmer.stem("dying"));
+/// ```
+///
+/// # Note
+///
+/// - Words are expected to be lowercase, as produced by `RougeTokenizer`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NltkPorterStemmer;
+
+impl Stemmer for NltkPorterStemmer {
+    fn stem(&self, word: &str) -> String {
+        porter_stem(word, true)
+    }
+}
+
+/// The irregular forms of the NLTK extensions, which are not stemmed by the rules.
+const NLTK_IRREGULAR_FORMS: [(&str, &str); 16] = [
+    ("sky", "sky"), ("skies", "sky"), ("dying", "die"), ("lying", "lie"), ("tying", "tie"),
+    ("news", "news"), ("innings", "inning"), ("inning", "inning"), ("outings", "outing"), ("outing", "outing"),
+    ("cannings", "canning"), ("canning", "canning"), ("howe", "howe"), ("proceed", "proceed"),
+    ("exceed", "exceed"), ("succeed", "succeed"),
+];
+
+/// Stems a word with the Porter stemmer, with the NLTK extensions if `nltk` is set.
+fn porter_stem(word: &str, nltk: bool) -> String {
+    if nltk {
+        if let Some((_, stem)) = NLTK_IRREGULAR_FORMS.iter().find(|(form, _)| *form == word) {
+            return stem.to_string();
+        }
+    }
+    let mut porter = Porter{b: word.chars().collect(), j: 0, nltk};
+    if porter.b.len() <= 2 {
+        return word.to_string();
+    }
+    porter.step1ab();
+    if porter.b.len() > 1 {
+        porter.step1c();
+        porter.step2();
+        porter.step3();
+        porter.step4();
+        porter.step5();
+    }
+    porter.b.into_iter().collect()
+}
+
+/// The state of the Porter stemmer: the word being stemmed, and the length of its stem
+/// once a suffix is matched by `ends`.
+struct Porter {
+    b: Vec<char>,
+    j: usize,
+    nltk: bool,
+}
+
+impl Porter {
+    /// Whether `b[i]` is a consonant, where "y" is a consonant unless it follows a consonant.
+    fn cons(&self, i: usize) -> bool {
+        match self.b[i] {
+            'a' | 'e' | 'i' | 'o' | 'u' => false,
+            'y' => i == 0 || !self.cons(i - 1),
+            _ => true,
+        }
+    }
+
+    /// The measure of the stem, i.e. the number of vowel-consonant sequences in `b[..j]`.
+    fn m(&self) -> usize {
+        let mut n: usize = 0;
+        let mut i: usize = 0;
+        // skip the leading consonants
+        while i < self.j && self.cons(i) {
+            i += 1;
+        }
+        loop {
+            while i < self.j && !self.cons(i) {
+                i += 1;
+            }
+            if i >= self.j {
+                return n;
+            }
+            n += 1;
+            while i < self.j && self.cons(i) {
+                i += 1;
+            }
+        }
+    }
+
+    /// Whether the stem `b[..j]` contains a vowel.
+    fn vowel_in_stem(&self) -> bool {
+        (0..self.j).any(|i| !self.cons(i))
+    }
+
+    /// Whether `b[i - 1..=i]` is a double consonant.
+    fn doublec(&self, i: usize) -> bool {
+        i >= 1 && self.b[i] == self.b[i - 1] && self.cons(i)
+    }
+
+    /// Whether `b[i - 2..=i]` is consonant-vowel-consonant, where the last consonant is not "w", "x" or "y".
+    /// This is used to restore an "e" at the end of short words, e.g. "hop(e)".
+    /// With the NLTK extensions, a two letter word of a vowel and a consonant counts as well.
+    fn cvc(&self, i: usize) -> bool {
+        if self.nltk && i == 1 {
+            return !self.cons(0) && self.cons(1);
+        }
+        i >= 2 && self.cons(i) && !self.cons(i - 1) && self.cons(i - 2) && !matches!(self.b[i], 'w' | 'x' | 'y')
+    }
+
+    /// Whether the word ends with `suffix`, setting `j` to the length of the stem if so.
+    fn ends(&mut self, suffix: &str) -> bool {
+        let suffix: Vec<char> = suffix.chars().collect();
+        if !self.b.ends_with(&suffix) {
+            return false;
+        }
+        self.j = self.b.len() - suffix.len();
+        true
+    }
+
+    /// Replaces the suffix after the stem `b[..j]` with `s`.
+    fn set_to(&mut self, s: &str) {
+        self.b.truncate(self.j);
+        self.b.extend(s.chars());
+    }
+
+    /// Replaces the suffix with `s` if the measure of the stem is positive.
+    fn r(&mut self, s: &str) {
+        if self.m() > 0 {
+            self.set_to(s);
+        }
+    }
+
+    /// Replaces the first matching suffix of `rules` with its replacement, if the measure of the stem is positive.
+    fn replace_suffix(&mut self, rules: &[(&str, &str)]) {
+        for (suffix, replacement) in rules.iter() {
+            if self.ends(suffix) {
+                self.r(replacement);
+                return;
+            }
+        }
+    }
+
+    /// Removes plurals and "-ed" or "-ing", e.g. "caresses" -> "caress", "ponies" -> "poni", "meetings" -> "meet".
+    fn step1ab(&mut self) {
+        let len = self.b.len();
+        if self.nltk && len == 4 && self.ends("ies") {
+            self.set_to("ie");
+        } else if self.b[len - 1] == 's' {
+            if self.ends("sses") {
+                self.b.truncate(len - 2);
+            } else if self.ends("ies") {
+                self.set_to("i");
+            } else if self.b[len - 2] != 's' {
+                self.b.pop();
+            }
+        }
+        if self.nltk && self.ends("ied") {
+            self.set_to(if self.b.len() == 4 { "ie" } else { "i" });
+        } else if self.ends("eed") {
+            if self.m() > 0 {
+                self.b.pop();
+            }
+        } else if (self.ends("ed") || self.ends("ing")) && self.vowel_in_stem() {
+            self.b.truncate(self.j);
+            let last = self.b.len() - 1;
+            if self.ends("at") {
+                self.set_to("ate");
+            } else if self.ends("bl") {
+                self.set_to("ble");
+            } else if self.ends("iz") {
+                self.set_to("ize");
+            } else if self.doublec(last) {
+                if !matches!(self.b[last], 'l' | 's' | 'z') {
+                    self.b.pop();
+                }
+            } else if self.m() == 1 && self.cvc(last) {
+                self.set_to("e");
+            }
+        }
+    }
+
+    /// Turns a final "y" into "i" when there is another vowel in the stem,
+    /// or with the NLTK extensions when it follows a consonant which is not the first letter.
+    fn step1c(&mut self) {
+        if !self.ends("y") {
+            return;
+        }
+        let replace = if self.nltk { self.j > 1 && self.cons(self.j - 1) } else { self.vowel_in_stem() };
+        if replace {
+            let last = self.b.len() - 1;
+            self.b[last] = 'i';
+        }
+    }
+
+    /// Maps double suffixes to single ones, e.g. "-ization" (= "-ize" + "-ation") -> "-ize".
+    fn step2(&mut self) {
+        if self.nltk {
+            // "-alli" is replaced first, and the result goes through this step again, e.g. "-ationalli" -> "-ate"
+            if self.ends("alli") && self.m() > 0 {
+                self.set_to("al");
+                self.step2();
+                return;
+            }
+            if self.ends("fulli") {
+                self.r("ful");
+                return;
+            }
+            // the "l" of "logi" is part of the stem, so that short stems like "geo" are stemmed as well
+            if self.ends("logi") {
+                self.j += 1;
+                self.r("og");
+                return;
+            }
+        }
+        let rules: &[(&str, &str)] = match self.b[self.b.len() - 2] {
+            'a' => &[("ational", "ate"), ("tional", "tion")],
+            'c' => &[("enci", "ence"), ("anci", "ance")],
+            'e' => &[("izer", "ize")],
+            'l' => &[("bli", "ble"), ("alli", "al"), ("entli", "ent"), ("eli", "e"), ("ousli", "ous")],
+            'o' => &[("ization", "ize"), ("ation", "ate"), ("ator", "ate")],
+            's' => &[("alism", "al"), ("iveness", "ive"), ("fulness", "ful"), ("ousness", "ous")],
+            't' => &[("aliti", "al"), ("iviti", "ive"), ("biliti", "ble")],
+            'g' => &[("logi", "log")],
+            _ => &[],
+        };
+        self.replace_suffix(rules);
+    }
+
+    /// Handles "-ic-", "-full", "-ness" etc.
+    fn step3(&mut self) {
+        let rules: &[(&str, &str)] = match self.b[self.b.len() - 1] {
+            'e' => &[("icate", "ic"), ("ative", ""), ("alize", "al")],
+            'i' => &[("iciti", "ic")],
+            'l' => &[("ical", "ic"), ("ful", "")],
+            's' => &[("ness", "")],
+            _ => &[],
+        };
+        self.replace_suffix(rules);
+    }
+
+    /// Removes "-ant", "-ence" etc. when the measure of the stem is greater than 1.
+    fn step4(&mut self) {
+        let suffixes: &[&str] = match self.b[self.b.len() - 2] {
+            'a' => &["al"],
+            'c' => &["ance", "ence"],
+            'e' => &["er"],
+            'i' => &["ic"],
+            'l' => &["able", "ible"],
+            'n' => &["ant", "ement", "ment", "ent"],
+            'o' => &["ion", "ou"],
+            's' => &["ism"],
+            't' => &["ate", "iti"],
+            'u' => &["ous"],
+            'v' => &["ive"],
+            'z' => &["ize"],
+            _ => &[],
+        };
+        for suffix in suffixes.iter() {
+            if self.ends(suffix) {
+                // "-ion" is removed only after "s" or "t"
+                if *suffix == "ion" && !(self.j >= 1 && matches!(self.b[self.j - 1], 's' | 't')) {
+                    continue;
+                }
+                if self.m() > 1 {
+                    self.b.truncate(self.j);
+                }
+                return;
+            }
+        }
+    }
+
+    /// Removes a final "-e" and changes "-ll" to "-l" when the measure is greater than 1.
+    fn step5(&mut self) {
+        self.j = self.b.len();
+        if self.b[self.b.len() - 1] == 'e' {
+            let a = self.m();
+            if a > 1 || (a == 1 && !self.cvc(self.b.len() - 2)) {
+                self.b.pop();
+            }
+        }
+        // a removed "e" does not change the measure
+        self.j = self.b.len();
+        let last = self.b.len() - 1;
+        if self.b[last] == 'l' && self.doublec(last) && self.m() > 1 {
+            self.b.pop();
+        }
+    }
+}
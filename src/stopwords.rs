@@ -0,0 +1,144 @@
+//! Stopword removal, as done by ROUGE-1.5.5 with the `-s` flag.
+//!
+//! Lists are bundled for a few languages: the SMART list (Salton, 1971) for English, which is the one used by
+//! ROUGE-1.5.5, and the Snowball lists (as distributed with NLTK) for German, French and Spanish.
+//! Stopwords are removed from the tokens before the n-grams are created, by wrapping the tokenizer of a metric
+//! (`StopwordList::filter`).
+//!
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use crate::commons::Result;
+use crate::tokenizer::Tokenizer;
+
+/// The languages of the bundled stopword lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// The SMART list of 570 words, as used by ROUGE-1.5.5.
+    English,
+    /// The Snowball German list.
+    German,
+    /// The Snowball French list.
+    French,
+    /// The Snowball Spanish list.
+    Spanish,
+}
+
+/// A set of stopwords. Words are stored and looked up lowercased.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::stopwords::{Language, StopwordList};
+///
+/// let stopwords = StopwordList::for_language(Language::English);
+/// assert!(stopwords.contains("The"));
+/// assert!(!stopwords.contains("cat"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StopwordList {
+    words: HashSet<String>,
+}
+
+impl StopwordList {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        StopwordList::default()
+    }
+
+    /// Creates a list from the given words.
+    pub fn from_words(words: &[&str]) -> Self {
+        StopwordList{words: words.iter().map(|word| word.to_lowercase()).collect()}
+    }
+
+    /// Returns the bundled list of a language.
+    pub fn for_language(language: Language) -> Self {
+        let text = match language {
+            Language::English => include_str!("../data/stopwords/english.txt"),
+            Language::German => include_str!("../data/stopwords/german.txt"),
+            Language::French => include_str!("../data/stopwords/french.txt"),
+            Language::Spanish => include_str!("../data/stopwords/spanish.txt"),
+        };
+        StopwordList::parse(text)
+    }
+
+    /// Creates a list from a text with one word per line.
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Self {
+        let words: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        StopwordList::from_words(&words)
+    }
+
+    /// Loads a list from a file in the format of `StopwordList::parse`.
+    ///
+    /// ### Returns
+    ///
+    /// A `Result` containing the list if successful, or an error if the file cannot be read.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(StopwordList::parse(&fs::read_to_string(path)?))
+    }
+
+    /// Adds a word to the list.
+    pub fn insert(&mut self, word: &str) {
+        self.words.insert(word.to_lowercase());
+    }
+
+    /// Returns `true` if `word` is a stopword, ignoring case.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    /// Returns the number of stopwords.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if the list has no stopwords.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Wraps a tokenizer so that stopwords are removed from its tokens.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use text_score::rouge::rouge_n_with_tokenizer;
+    /// use text_score::stopwords::{Language, StopwordList};
+    /// use text_score::tokenizer::RougeTokenizer;
+    ///
+    /// let tokenizer = StopwordList::for_language(Language::English).filter(RougeTokenizer);
+    /// let score = rouge_n_with_tokenizer("the cat is on the mat", "a cat sat on a mat", 1, &tokenizer).unwrap();
+    /// assert_eq!(2.0 / 3.0, score.recall);
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// - To remove stopwords and stem, as ROUGE-1.5.5 with `-s -m`, wrap the filter in the `StemmingTokenizer`
+    ///   rather than the opposite, so that the words and not their stems are looked up.
+    pub fn filter<T: Tokenizer>(self, tokenizer: T) -> StopwordFilter<T> {
+        StopwordFilter{stopwords: self, tokenizer}
+    }
+}
+
+/// A tokenizer removing stopwords from the tokens of another tokenizer, see `StopwordList::filter`.
+#[derive(Debug, Clone)]
+pub struct StopwordFilter<T: Tokenizer> {
+    /// The stopwords removed.
+    pub stopwords: StopwordList,
+    tokenizer: T,
+}
+
+impl<T: Tokenizer> Tokenizer for StopwordFilter<T> {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.tokenizer
+            .tokenize(text)
+            .into_iter()
+            .filter(|token| !self.stopwords.contains(token))
+            .collect()
+    }
+}
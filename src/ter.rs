@@ -0,0 +1,358 @@
+//! Translation Edit Rate (TER), following Snover et al. (2006).
+//! The shift search and its ranking of candidates are the same as tercom and sacrebleu,
+//! but the edit distance is computed exactly instead of with a beam search,
+//! and the texts are compared case-sensitively (sacrebleu lowercases them by default).
+//!
+use std::cmp::min;
+use crate::commons::{MetricError, Result};
+use crate::edit_distance::EditOperation;
+use crate::tokenizer::{as_strs, Tokenizer, WhitespaceTokenizer};
+
+/// The maximum number of words shifted at once.
+const MAX_SHIFT_SIZE: usize = 10;
+/// The maximum distance between the positions of shifted words in the input and in the reference.
+const MAX_SHIFT_DIST: usize = 50;
+/// The maximum number of shift candidates checked per input.
+const MAX_SHIFT_CANDIDATES: usize = 1000;
+
+/// Represents a TER score together with the statistics it is computed from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerScore {
+    /// `num_edits / reference_len`, lower is better.
+    pub score: f32,
+    /// The number of edits, including shifts, against the closest reference.
+    pub num_edits: u32,
+    /// The number of shifts among `num_edits`.
+    pub num_shifts: u32,
+    /// The average number of words in the references.
+    pub reference_len: f32,
+}
+
+/// Computes the edit distance of an input to a reference, and the operations of the alignment.
+///
+/// Ties are broken the same way as tercom: hits and substitutions first, then insertions, then deletions.
+fn align(input: &[&str], reference: &[&str]) -> (u32, Vec<EditOperation>) {
+    let mut costs: Vec<Vec<u32>> = vec![vec![0; reference.len() + 1]; input.len() + 1];
+    let mut operations: Vec<Vec<EditOperation>> = vec![vec![EditOperation::Hit; reference.len() + 1]; input.len() + 1];
+    for j in 1..=reference.len() {
+        costs[0][j] = j as u32;
+        operations[0][j] = EditOperation::Deletion;
+    }
+    for i in 1..=input.len() {
+        costs[i][0] = i as u32;
+        operations[i][0] = EditOperation::Insertion;
+        for j in 1..=reference.len() {
+            let (cost_sub, op_sub) = if input[i - 1] == reference[j - 1] {
+                (0, EditOperation::Hit)
+            } else {
+                (1, EditOperation::Substitution)
+            };
+            let candidates = [
+                (costs[i - 1][j - 1] + cost_sub, op_sub),
+                (costs[i - 1][j] + 1, EditOperation::Insertion),
+                (costs[i][j - 1] + 1, EditOperation::Deletion),
+            ];
+            let (mut best_cost, mut best_op) = candidates[0];
+            for (cost, op) in candidates.into_iter().skip(1) {
+                if cost < best_cost {
+                    best_cost = cost;
+                    best_op = op;
+                }
+            }
+            costs[i][j] = best_cost;
+            operations[i][j] = best_op;
+        }
+    }
+
+    let mut trace: Vec<EditOperation> = Vec::new();
+    let (mut i, mut j) = (input.len(), reference.len());
+    while i > 0 || j > 0 {
+        let op = operations[i][j];
+        trace.push(op);
+        match op {
+            EditOperation::Hit | EditOperation::Substitution => {
+                i -= 1;
+                j -= 1;
+            }
+            EditOperation::Insertion => i -= 1,
+            EditOperation::Deletion => j -= 1,
+        }
+    }
+    trace.reverse();
+    (costs[input.len()][reference.len()], trace)
+}
+
+/// Moves `words[start..start + length]` so that it starts right before `words[target]` of the original order.
+fn perform_shift<'a>(words: &[&'a str], start: usize, length: usize, target: usize) -> Vec<&'a str> {
+    let mut shifted: Vec<&str> = Vec::with_capacity(words.len());
+    if target < start {
+        shifted.extend_from_slice(&words[..target]);
+        shifted.extend_from_slice(&words[start..start + length]);
+        shifted.extend_from_slice(&words[target..start]);
+        shifted.extend_from_slice(&words[start + length..]);
+    } else if target > start + length {
+        shifted.extend_from_slice(&words[..start]);
+        shifted.extend_from_slice(&words[start + length..target]);
+        shifted.extend_from_slice(&words[start..start + length]);
+        shifted.extend_from_slice(&words[target..]);
+    } else {
+        // as in sacrebleu, the block moves right by `target - start` words, at most to the end
+        let end = min(length + target, words.len());
+        shifted.extend_from_slice(&words[..start]);
+        shifted.extend_from_slice(&words[start + length..end]);
+        shifted.extend_from_slice(&words[start..start + length]);
+        shifted.extend_from_slice(&words[end..]);
+    }
+    shifted
+}
+
+/// Finds the best shift of the input, i.e. the one reducing the edit distance the most.
+///
+/// Returns the reduction of the edit distance and the shifted input, or 0 and `None` if there is no candidate.
+fn best_shift<'a>(input: &[&'a str], reference: &[&str], checked_candidates: &mut usize) -> (i64, Option<Vec<&'a str>>) {
+    let (pre_score, trace) = align(input, reference);
+
+    // alignment of each reference word to an input position (-1 before the first word), and which words are errors
+    let mut alignment: Vec<i64> = Vec::with_capacity(reference.len());
+    let mut input_errors: Vec<bool> = Vec::with_capacity(input.len());
+    let mut reference_errors: Vec<bool> = Vec::with_capacity(reference.len());
+    let mut input_pos: i64 = -1;
+    for op in trace {
+        match op {
+            EditOperation::Hit | EditOperation::Substitution => {
+                input_pos += 1;
+                alignment.push(input_pos);
+                input_errors.push(op == EditOperation::Substitution);
+                reference_errors.push(op == EditOperation::Substitution);
+            }
+            EditOperation::Insertion => {
+                input_pos += 1;
+                input_errors.push(true);
+            }
+            EditOperation::Deletion => {
+                alignment.push(input_pos);
+                reference_errors.push(true);
+            }
+        }
+    }
+
+    // ranked by (reduction, length, earliest start, earliest target), as tercom does
+    let mut best_rank: Option<(i64, usize, i64, i64)> = None;
+    let mut best_words: Vec<&str> = Vec::new();
+    'search: for start_h in 0..input.len() {
+        for start_r in 0..reference.len() {
+            if start_h.abs_diff(start_r) > MAX_SHIFT_DIST {
+                continue;
+            }
+            let mut length: usize = 0;
+            while length < MAX_SHIFT_SIZE
+                && start_h + length < input.len()
+                && start_r + length < reference.len()
+                && input[start_h + length] == reference[start_r + length]
+            {
+                length += 1;
+
+                // shift only wrong words to a position where the reference is not matched yet
+                if !input_errors[start_h..start_h + length].contains(&true)
+                    || !reference_errors[start_r..start_r + length].contains(&true)
+                {
+                    continue;
+                }
+                // don't shift within the words themselves
+                let aligned = alignment[start_r];
+                if start_h as i64 <= aligned && aligned < (start_h + length) as i64 {
+                    continue;
+                }
+
+                let mut prev_target: i64 = -1;
+                for offset in -1..length as i64 {
+                    let target: i64 = if start_r as i64 + offset == -1 {
+                        0
+                    } else if ((start_r as i64 + offset) as usize) < reference.len() {
+                        alignment[(start_r as i64 + offset) as usize] + 1
+                    } else {
+                        break;
+                    };
+                    if target == prev_target {
+                        continue;
+                    }
+                    prev_target = target;
+
+                    let shifted = perform_shift(input, start_h, length, target as usize);
+                    let rank = (pre_score as i64 - align(&shifted, reference).0 as i64, length, -(start_h as i64), -target);
+                    *checked_candidates += 1;
+                    if best_rank.is_none_or(|best| rank > best) {
+                        best_rank = Some(rank);
+                        best_words = shifted;
+                    }
+                }
+                if *checked_candidates >= MAX_SHIFT_CANDIDATES {
+                    break 'search;
+                }
+            }
+        }
+    }
+
+    match best_rank {
+        Some((reduction, _, _, _)) => (reduction, Some(best_words)),
+        None => (0, None),
+    }
+}
+
+/// Computes the number of edits and shifts needed to turn an input into a reference.
+fn translation_edit_rate(input: &[&str], reference: &[&str]) -> (u32, u32) {
+    if reference.is_empty() {
+        return (input.len() as u32, 0);
+    }
+
+    let mut words: Vec<&str> = input.to_vec();
+    let mut num_shifts: u32 = 0;
+    let mut checked_candidates: usize = 0;
+    // shift greedily until shifts stop reducing the edit distance
+    loop {
+        let (reduction, shifted) = best_shift(&words, reference, &mut checked_candidates);
+        if checked_candidates >= MAX_SHIFT_CANDIDATES || reduction <= 0 {
+            break;
+        }
+        num_shifts += 1;
+        words = shifted.unwrap();
+    }
+
+    (align(&words, reference).0 + num_shifts, num_shifts)
+}
+
+/// Computes corpus-level TER scores for the given inputs and their references.
+///
+/// TER is the minimum number of edits (insertions, deletions, substitutions and shifts of word sequences)
+/// needed to turn an input into a reference, divided by the average reference length.
+///
+/// ### Arguments
+///
+/// * `inputs` - The input texts to be evaluated.
+/// * `references` - One or more reference texts for each input, in the same order as `inputs`.
+///
+/// ### Returns
+///
+/// A `Result` containing a `TerScore` if successful, or an error message if the number of inputs
+/// and references differ, or an input has no reference.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::ter::corpus_ter;
+///
+/// let inputs = ["d e f g h a b c", "the cat sat"];
+/// let references = [vec!["a b c d e f g h"], vec!["the cat sat on the mat", "a cat sat"]];
+///
+/// let score = corpus_ter(&inputs, &references).unwrap();
+/// println!("TER: {}", score.score); // (1 + 1) / (8 + 4.5)
+/// ```
+///
+/// # Note
+///
+/// - The texts are split into words on whitespace, see `corpus_ter_with_tokenizer` for other tokenizers.
+/// - With multiple references, the fewest edits against any reference are used,
+///   but the length is averaged over all the references.
+/// - The edits and the lengths are summed over the corpus before the score is computed.
+/// - If the references are empty, the score is 1 if there is any edit and 0 otherwise.
+pub fn corpus_ter(inputs: &[&str], references: &[Vec<&str>]) -> Result<TerScore> {
+    corpus_ter_with_tokenizer(inputs, references, &WhitespaceTokenizer)
+}
+
+/// Computes corpus-level TER scores, splitting the texts into words with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `inputs` - The input texts to be evaluated.
+/// * `references` - One or more reference texts for each input, in the same order as `inputs`.
+/// * `tokenizer` - The tokenizer splitting every text into words.
+///
+/// ### Returns
+///
+/// A `Result` containing a `TerScore` if successful, see `corpus_ter`.
+pub fn corpus_ter_with_tokenizer(inputs: &[&str], references: &[Vec<&str>], tokenizer: &dyn Tokenizer) -> Result<TerScore> {
+    if inputs.len() != references.len() {
+        return Err(MetricError::LengthMismatch{inputs: inputs.len(), references: references.len()});
+    }
+    if let Some(i) = references.iter().position(|r| r.is_empty()) {
+        return Err(MetricError::MissingReference(i));
+    }
+
+    let mut num_edits: u32 = 0;
+    let mut num_shifts: u32 = 0;
+    let mut reference_len: f32 = 0.0;
+    for (input, input_references) in inputs.iter().zip(references.iter()) {
+        let input_tokens = tokenizer.tokenize(input);
+        let input_words: Vec<&str> = as_strs(&input_tokens);
+
+        let mut best: (u32, u32) = (u32::MAX, 0);
+        let mut total_len: usize = 0;
+        for reference in input_references.iter() {
+            let reference_tokens = tokenizer.tokenize(reference);
+            let reference_words: Vec<&str> = as_strs(&reference_tokens);
+            let result = translation_edit_rate(&input_words, &reference_words);
+            if result.0 < best.0 {
+                best = result;
+            }
+            total_len += reference_words.len();
+        }
+
+        num_edits += best.0;
+        num_shifts += best.1;
+        reference_len += total_len as f32 / input_references.len() as f32;
+    }
+
+    let score = if reference_len > 0.0 {
+        num_edits as f32 / reference_len
+    } else if num_edits > 0 {
+        1.0
+    } else {
+        0.0
+    };
+
+    Ok(TerScore{score, num_edits, num_shifts, reference_len})
+}
+
+/// Computes sentence-level TER scores for a given input and its references.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `references` - One or more reference texts.
+///
+/// ### Returns
+///
+/// A `Result` containing a `TerScore` if successful, or an error message if `references` is empty.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::ter::sentence_ter;
+///
+/// // a single shift of "a b c"
+/// let score = sentence_ter("d e f g h a b c", &["a b c d e f g h"]).unwrap();
+/// assert_eq!(1, score.num_edits);
+/// assert_eq!(1, score.num_shifts);
+/// ```
+///
+/// # Note
+///
+/// - This is the same as `corpus_ter` with a single input.
+pub fn sentence_ter(input: &str, references: &[&str]) -> Result<TerScore> {
+    corpus_ter(&[input], &[references.to_vec()])
+}
+
+/// Computes sentence-level TER scores, splitting the texts into words with the given tokenizer.
+///
+/// ### Arguments
+///
+/// * `input` - The input text to be evaluated.
+/// * `references` - One or more reference texts.
+/// * `tokenizer` - The tokenizer splitting every text into words.
+///
+/// ### Returns
+///
+/// A `Result` containing a `TerScore` if successful, or an error message if `references` is empty.
+pub fn sentence_ter_with_tokenizer(input: &str, references: &[&str], tokenizer: &dyn Tokenizer) -> Result<TerScore> {
+    corpus_ter_with_tokenizer(&[input], &[references.to_vec()], tokenizer)
+}
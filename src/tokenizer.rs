@@ -0,0 +1,189 @@
+//! Tokenizers splitting a text into the tokens compared by the metrics.
+//!
+//! Every metric working on words has a `_with_tokenizer` variant taking a `Tokenizer`,
+//! and the variant without it uses `WhitespaceTokenizer`.
+//! Any `Fn(&str) -> Vec<String>` can be used as a `Tokenizer` as well.
+//!
+use crate::commons::Result;
+use regex::Regex;
+use crate::stemmer::{NltkPorterStemmer, Stemmer};
+
+/// Tokens of at most this many characters are not stemmed by `rouge_score`.
+pub const ROUGE_MIN_STEM_LEN: usize = 3;
+
+/// Splits a text into tokens.
+pub trait Tokenizer {
+    /// Returns the tokens of `text`, in order.
+    fn tokenize(&self, text: &str) -> Vec<String>;
+}
+
+impl<F> Tokenizer for F
+where
+    F: Fn(&str) -> Vec<String>,
+{
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self(text)
+    }
+}
+
+/// Borrows tokens as string slices, as taken by `create_ngrams` and the other building blocks of the metrics.
+pub(crate) fn as_strs(tokens: &[String]) -> Vec<&str> {
+    tokens.iter().map(String::as_str).collect()
+}
+
+/// Splits a text on whitespace, the default of every metric.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::tokenizer::{Tokenizer, WhitespaceTokenizer};
+///
+/// assert_eq!(vec!["this", "is", "a", "case."], WhitespaceTokenizer.tokenize("this is  a case."));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.split_whitespace().map(str::to_string).collect()
+    }
+}
+
+/// Takes every match of a regular expression as a token.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::tokenizer::{RegexTokenizer, Tokenizer};
+///
+/// let tokenizer = RegexTokenizer::new(r"[\w']+").unwrap();
+/// assert_eq!(vec!["don't", "stop", "now"], tokenizer.tokenize("don't stop, now!"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegexTokenizer {
+    pattern: Regex,
+}
+
+impl RegexTokenizer {
+    /// Creates a tokenizer from a regular expression matching the tokens.
+    ///
+    /// ### Returns
+    ///
+    /// A `Result` containing the tokenizer if successful, or an error if `pattern` is not a valid regular expression.
+    pub fn new(pattern: &str) -> Result<Self> {
+        Ok(RegexTokenizer{pattern: Regex::new(pattern)?})
+    }
+}
+
+impl Tokenizer for RegexTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.pattern.find_iter(text).map(|m| m.as_str().to_string()).collect()
+    }
+}
+
+/// Splits a text on whitespace, and splits punctuation from words, like nltk's `wordpunct_tokenize`.
+///
+/// A token is either a run of alphanumeric characters and underscores, or a run of other non-whitespace characters.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::tokenizer::{PunctuationTokenizer, Tokenizer};
+///
+/// assert_eq!(vec!["this", "is", "a", "case", "."], PunctuationTokenizer.tokenize("this is a case."));
+/// assert_eq!(vec!["don", "'", "t", "...", "stop"], PunctuationTokenizer.tokenize("don't... stop"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PunctuationTokenizer;
+
+impl Tokenizer for PunctuationTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let mut tokens: Vec<String> = Vec::new();
+        let mut current = String::new();
+        for c in text.chars() {
+            let continues = current.chars().next().is_some_and(|first| is_word(first) == is_word(c));
+            if (c.is_whitespace() || !continues) && !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            if !c.is_whitespace() {
+                current.push(c);
+            }
+        }
+        if !current.is_empty() {
+            tokens.push(current);
+        }
+        tokens
+    }
+}
+
+/// Tokenizes a text the same way as the google research `rouge_score` package (without stemming).
+///
+/// The text is lowercased, every character other than `a-z` and `0-9` is replaced by a space,
+/// and the result is split on whitespace.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::tokenizer::{RougeTokenizer, Tokenizer};
+///
+/// assert_eq!(vec!["this", "is", "a", "case"], RougeTokenizer.tokenize("This is a case."));
+/// assert_eq!(vec!["don", "t", "stop"], RougeTokenizer.tokenize("Don't stop!"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RougeTokenizer;
+
+impl Tokenizer for RougeTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_lowercase() || c.is_ascii_digit() { c } else { ' ' })
+            .collect::<String>()
+            .split_whitespace()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Stems the tokens of another tokenizer.
+///
+/// ### Examples
+///
+/// ```
+/// use text_score::tokenizer::{StemmingTokenizer, Tokenizer};
+///
+/// // same as rouge_score with `use_stemmer=True`
+/// let tokenizer = StemmingTokenizer::rouge();
+/// assert_eq!(vec!["the", "cat", "are", "run"], tokenizer.tokenize("The cats are running."));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StemmingTokenizer<T: Tokenizer, S: Stemmer> {
+    tokenizer: T,
+    stemmer: S,
+    min_len: usize,
+}
+
+impl<T: Tokenizer, S: Stemmer> StemmingTokenizer<T, S> {
+    /// Creates a tokenizer stemming the tokens of `tokenizer` which have more than `min_len` characters.
+    pub fn new(tokenizer: T, stemmer: S, min_len: usize) -> Self {
+        StemmingTokenizer{tokenizer, stemmer, min_len}
+    }
+}
+
+impl StemmingTokenizer<RougeTokenizer, NltkPorterStemmer> {
+    /// Creates the tokenizer of `rouge_score` with `use_stemmer=True`: `RougeTokenizer` followed by the
+    /// Porter stemmer of NLTK, applied to the tokens longer than `ROUGE_MIN_STEM_LEN` characters.
+    pub fn rouge() -> Self {
+        StemmingTokenizer::new(RougeTokenizer, NltkPorterStemmer, ROUGE_MIN_STEM_LEN)
+    }
+}
+
+impl<T: Tokenizer, S: Stemmer> Tokenizer for StemmingTokenizer<T, S> {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.tokenizer
+            .tokenize(text)
+            .into_iter()
+            .map(|token| if token.chars().count() > self.min_len { self.stemmer.stem(&token) } else { token })
+            .collect()
+    }
+}
@@ -0,0 +1,150 @@
+use approx::assert_abs_diff_eq;
+use proptest::prelude::*;
+use text_score::bleu::{brevity_penalty, corpus_bleu, sentence_bleu, Smoothing, DEFAULT_MAX_ORDER};
+use text_score::commons::MetricError;
+
+// examples from Papineni et al. (2002), as used in the nltk documentation
+const HYPOTHESIS1: &str = "It is a guide to action which ensures that the military always obeys the commands of the party";
+const REFERENCE1A: &str = "It is a guide to action that ensures that the military will forever heed Party commands";
+const REFERENCE1B: &str = "It is the guiding principle which guarantees the military forces always being under the command of the Party";
+const REFERENCE1C: &str = "It is the practical guide for the army always to heed the directions of the party";
+const HYPOTHESIS2: &str = "he read the book because he was interested in world history";
+const REFERENCE2A: &str = "he was interested in world history because he read the book";
+
+#[test]
+fn test_brevity_penalty(){
+    assert_eq!(1.0, brevity_penalty(10, 10));
+    assert_eq!(1.0, brevity_penalty(12, 10));
+    assert_abs_diff_eq!((1.0f32 - 10.0 / 8.0).exp(), brevity_penalty(8, 10), epsilon = 1e-6);
+    assert_eq!(0.0, brevity_penalty(0, 10));
+}
+#[test]
+fn test_sentence_bleu(){
+    // identical: 1.0
+    let score = sentence_bleu("the cat is on the mat", &["the cat is on the mat"], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert_abs_diff_eq!(1.0, score.score, epsilon = 1e-6);
+
+    let score = sentence_bleu(HYPOTHESIS1, &[REFERENCE1A, REFERENCE1B, REFERENCE1C], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert_abs_diff_eq!(0.504567, score.score, epsilon = 1e-5);
+    assert_abs_diff_eq!(17.0 / 18.0, score.precisions[0], epsilon = 1e-6);
+    assert_abs_diff_eq!(10.0 / 17.0, score.precisions[1], epsilon = 1e-6);
+    assert_eq!(18, score.input_len);
+    assert_eq!(18, score.reference_len);
+    assert_eq!(1.0, score.brevity_penalty);
+
+    // no 4-gram match: 0.0
+    let score = sentence_bleu("this is a fest", &["this is a test"], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert_eq!(0.0, score.score);
+    assert_abs_diff_eq!(0.75, score.precisions[0], epsilon = 1e-6);
+    assert_eq!(0.0, score.precisions[3]);
+
+    // lower max order
+    let score = sentence_bleu("this is a fest", &["this is a test"], 2, Smoothing::None).unwrap();
+    assert_abs_diff_eq!((0.75f32 * 2.0 / 3.0).sqrt(), score.score, epsilon = 1e-6);
+
+    // only the orders with n-grams in the input are averaged, unlike corpus_bleu without smoothing
+    let score = sentence_bleu("the cat", &["the cat"], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert_eq!(1.0, score.score);
+    assert_eq!(0.0, corpus_bleu(&["the cat"], &[vec!["the cat"]], DEFAULT_MAX_ORDER, Smoothing::None).unwrap().score);
+
+    // closest reference length, shorter one on ties
+    let score = sentence_bleu("a b c", &["a b c d e", "a b", "a b c d"], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert_eq!(2, score.reference_len);
+
+    assert!(matches!(sentence_bleu("the cat", &[], DEFAULT_MAX_ORDER, Smoothing::None), Err(MetricError::MissingReference(0))));
+    assert!(matches!(sentence_bleu("the cat", &["the cat"], 0, Smoothing::None), Err(MetricError::InvalidN(0))));
+    for smoothing in [Smoothing::AddEpsilon(-0.1), Smoothing::AddEpsilon(f32::NAN), Smoothing::Floor(-1.0), Smoothing::Floor(f32::INFINITY)] {
+        assert!(matches!(sentence_bleu("the cat", &["the cat"], DEFAULT_MAX_ORDER, smoothing), Err(MetricError::InvalidWeight(_))));
+    }
+}
+#[test]
+fn test_corpus_bleu(){
+    let inputs = [HYPOTHESIS1, HYPOTHESIS2];
+    let references = [vec![REFERENCE1A, REFERENCE1B, REFERENCE1C], vec![REFERENCE2A]];
+
+    let score = corpus_bleu(&inputs, &references, DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert_abs_diff_eq!(0.592078, score.score, epsilon = 1e-5);
+    assert_eq!(29, score.input_len);
+
+    // corpus-level is not the average of sentence-level scores
+    let first = sentence_bleu(HYPOTHESIS1, &references[0], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    let second = sentence_bleu(HYPOTHESIS2, &references[1], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert!((score.score - (first.score + second.score) / 2.0).abs() > 1e-3);
+
+    // short inputs are fine
+    let score = corpus_bleu(&["the", ""], &[vec!["the cat"], vec!["a dog"]], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert_eq!(0.0, score.score);
+
+    // no input has 4 tokens: all orders are averaged even with smoothing, as sacrebleu's corpus_bleu does
+    let score = corpus_bleu(&["the cat", "a b c"], &[vec!["the cat"], vec!["a b c"]], DEFAULT_MAX_ORDER, Smoothing::NistGeometric).unwrap();
+    assert_eq!(0.0, score.score);
+    assert_eq!(0.0, corpus_bleu(&["the cat"], &[vec!["the cat"]], DEFAULT_MAX_ORDER, Smoothing::NistGeometric).unwrap().score);
+
+    assert!(matches!(corpus_bleu(&inputs, &references[..1], DEFAULT_MAX_ORDER, Smoothing::None), Err(MetricError::LengthMismatch{inputs: 2, references: 1})));
+}
+#[test]
+fn test_sentence_bleu_smoothing(){
+    // "this is a fest": 3/4, 2/3, 1/2 and 0/1 matches
+    let (input, references) = ("this is a fest", ["this is a test"]);
+
+    // test case from sacrebleu test_api.py: floor of 0.01
+    let score = sentence_bleu(input, &references, DEFAULT_MAX_ORDER, Smoothing::Floor(0.01)).unwrap();
+    assert_abs_diff_eq!(0.223607, score.score, epsilon = 1e-5);
+    let score = sentence_bleu(input, &references, DEFAULT_MAX_ORDER, Smoothing::AddEpsilon(0.01)).unwrap();
+    assert_abs_diff_eq!(0.223607, score.score, epsilon = 1e-5);
+    // floor is an alias of add-epsilon
+    for (i, r) in [(input, references[0]), (HYPOTHESIS2, REFERENCE2A), ("a b", "a c")] {
+        let floor = sentence_bleu(i, &[r], DEFAULT_MAX_ORDER, Smoothing::Floor(0.1)).unwrap();
+        assert_eq!(sentence_bleu(i, &[r], DEFAULT_MAX_ORDER, Smoothing::AddEpsilon(0.1)).unwrap(), floor);
+    }
+
+    // 1/2 for the first order without match
+    let score = sentence_bleu(input, &references, DEFAULT_MAX_ORDER, Smoothing::NistGeometric).unwrap();
+    assert_abs_diff_eq!(0.5, score.precisions[3], epsilon = 1e-6);
+    assert_abs_diff_eq!(0.125f32.powf(0.25), score.score, epsilon = 1e-6);
+
+    // (m + 1) / (t + 1) from bigrams on
+    let score = sentence_bleu(input, &references, DEFAULT_MAX_ORDER, Smoothing::AddOne).unwrap();
+    assert_abs_diff_eq!(0.75, score.precisions[0], epsilon = 1e-6);
+    assert_abs_diff_eq!(0.75, score.precisions[1], epsilon = 1e-6);
+    assert_abs_diff_eq!(2.0 / 3.0, score.precisions[2], epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5, score.precisions[3], epsilon = 1e-6);
+
+    // invcnt = 5 / ln(4)
+    let score = sentence_bleu(input, &references, DEFAULT_MAX_ORDER, Smoothing::LengthDependent).unwrap();
+    assert_abs_diff_eq!(4.0f32.ln() / 5.0, score.precisions[3], epsilon = 1e-6);
+
+    // matched orders are not changed
+    for smoothing in [Smoothing::AddEpsilon(0.1), Smoothing::NistGeometric, Smoothing::LengthDependent, Smoothing::Floor(0.1)] {
+        let smoothed = sentence_bleu(HYPOTHESIS1, &[REFERENCE1A, REFERENCE1B, REFERENCE1C], DEFAULT_MAX_ORDER, smoothing).unwrap();
+        assert_abs_diff_eq!(0.504567, smoothed.score, epsilon = 1e-5);
+    }
+
+    // orders longer than the input are left out of the average
+    let score = sentence_bleu("the cat", &["the cat sat"], DEFAULT_MAX_ORDER, Smoothing::NistGeometric).unwrap();
+    assert_abs_diff_eq!((-0.5f32).exp(), score.score, epsilon = 1e-6);
+    for smoothing in [Smoothing::AddEpsilon(0.1), Smoothing::AddOne, Smoothing::NistGeometric, Smoothing::LengthDependent, Smoothing::Floor(0.1)] {
+        let score = sentence_bleu("the cat sat", &["the cat sat"], DEFAULT_MAX_ORDER, smoothing).unwrap();
+        assert_abs_diff_eq!(1.0, score.score, epsilon = 1e-6);
+    }
+}
+#[test]
+fn test_corpus_bleu_smoothing(){
+    let inputs = ["this is a fest", "the cat sat on a mat"];
+    let references = [vec!["this is a test"], vec!["the cat sat on the mat"]];
+
+    // 4-grams: 0 + 1 matches out of 1 + 3, not smoothed
+    let score = corpus_bleu(&inputs, &references, DEFAULT_MAX_ORDER, Smoothing::NistGeometric).unwrap();
+    let unsmoothed = corpus_bleu(&inputs, &references, DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert_abs_diff_eq!(0.25, score.precisions[3], epsilon = 1e-6);
+    assert_eq!(unsmoothed, score);
+}
+proptest! {
+    #[test]
+    fn test_bleu_short_inputs(input in "([a-c] ?){0,4}", reference in "([a-c] ?){0,4}", max_order in 1usize..6) {
+        for smoothing in [Smoothing::None, Smoothing::AddEpsilon(0.1), Smoothing::AddOne, Smoothing::NistGeometric, Smoothing::LengthDependent] {
+            let score = sentence_bleu(&input, &[&reference], max_order, smoothing).unwrap();
+            prop_assert!((0.0..=1.0).contains(&score.score), "{:?}", score);
+        }
+    }
+}
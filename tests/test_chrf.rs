@@ -0,0 +1,88 @@
+use approx::assert_abs_diff_eq;
+use proptest::prelude::*;
+use text_score::chrf::{corpus_chrf, sentence_chrf, ChrfConfig};
+use text_score::commons::MetricError;
+
+const INPUT1: &str = "risk assessment must be made of those who are qualified and expertise in the sector - these are the scientists .";
+const REFERENCE1: &str = "risk assessment has to be undertaken by those who are qualified and expert in that area - that is the scientists .";
+const INPUT2: &str = "Die Beziehung zwischen Obama und Netanjahu ist nicht gerade freundlich.";
+const REFERENCE2: &str = "Das Verhältnis zwischen Obama und Netanyahu ist nicht gerade freundschaftlich.";
+
+#[test]
+fn test_sentence_chrf(){
+    let config = ChrfConfig::default();
+
+    // test cases from sacrebleu test_chrf.py
+    assert_eq!(1.0, sentence_chrf("a", &["a"], &config).unwrap().f1);
+    assert_eq!(0.0, sentence_chrf("", &["reference"], &config).unwrap().f1);
+    assert_eq!(0.0, sentence_chrf("", &["c"], &config).unwrap().f1);
+    assert_eq!(1.0, sentence_chrf("a b c", &["a b c"], &config).unwrap().f1);
+    assert_abs_diff_eq!(0.25, sentence_chrf("aa", &["ab"], &config).unwrap().f1, epsilon = 1e-6);
+
+    // whitespace is ignored by default
+    assert_eq!(1.0, sentence_chrf("a b c", &["abc"], &config).unwrap().f1);
+    let with_whitespace = ChrfConfig{include_whitespace: true, ..ChrfConfig::default()};
+    assert!(sentence_chrf("a b c", &["abc"], &with_whitespace).unwrap().f1 < 1.0);
+
+    let score = sentence_chrf(INPUT1, &[REFERENCE1], &config).unwrap();
+    assert_abs_diff_eq!(0.634317, score.f1, epsilon = 1e-5);
+    let score = sentence_chrf(INPUT2, &[REFERENCE2], &config).unwrap();
+    assert_abs_diff_eq!(0.648181, score.f1, epsilon = 1e-5);
+    let score = sentence_chrf(INPUT2, &[REFERENCE2], &with_whitespace).unwrap();
+    assert_abs_diff_eq!(0.679832, score.f1, epsilon = 1e-5);
+
+    // beta weighs recall over precision: p=1, r=1/2
+    let score = sentence_chrf("a", &["ab"], &ChrfConfig{char_order: 1, ..ChrfConfig::default()}).unwrap();
+    assert_abs_diff_eq!(1.0, score.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5, score.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(5.0 * 0.5 / (4.0 + 0.5), score.f1, epsilon = 1e-6);
+    assert_eq!(2.0, score.beta);
+    let score = sentence_chrf("a", &["ab"], &ChrfConfig{char_order: 1, beta: 1.0, ..ChrfConfig::default()}).unwrap();
+    assert_abs_diff_eq!(2.0 / 3.0, score.f1, epsilon = 1e-6);
+
+    // the best reference is used
+    let score = sentence_chrf(INPUT2, &["something else", REFERENCE2], &config).unwrap();
+    assert_abs_diff_eq!(0.648181, score.f1, epsilon = 1e-5);
+
+    assert!(sentence_chrf("a", &[], &config).is_err());
+    assert!(sentence_chrf("a", &["a"], &ChrfConfig{char_order: 0, ..ChrfConfig::default()}).is_err());
+    for beta in [f32::NAN, f32::INFINITY] {
+        let result = sentence_chrf("abc", &["abd"], &ChrfConfig{beta, ..ChrfConfig::default()});
+        assert!(matches!(result, Err(MetricError::InvalidWeight(_))));
+    }
+}
+#[test]
+fn test_sentence_chrf_plus_plus(){
+    let config = ChrfConfig::chrf_plus_plus();
+
+    let score = sentence_chrf(INPUT1, &[REFERENCE1], &config).unwrap();
+    assert_abs_diff_eq!(0.592366, score.f1, epsilon = 1e-5);
+    let score = sentence_chrf(INPUT2, &[REFERENCE2], &config).unwrap();
+    assert_abs_diff_eq!(0.615872, score.f1, epsilon = 1e-5);
+
+    // punctuation is split off from words: "freundlich." matches "freundlich ."
+    let score = sentence_chrf("freundlich.", &["freundlich ."], &config).unwrap();
+    assert_eq!(1.0, score.f1);
+}
+#[test]
+fn test_corpus_chrf(){
+    let config = ChrfConfig::default();
+
+    // statistics are summed over the corpus
+    let score = corpus_chrf(&["a", "b"], &[vec!["a"], vec!["c"]], &config).unwrap();
+    assert_abs_diff_eq!(0.5, score.f1, epsilon = 1e-6);
+
+    let score = corpus_chrf(&[INPUT1, INPUT2], &[vec![REFERENCE1], vec![REFERENCE2]], &config).unwrap();
+    assert!(score.f1 > 0.6 && score.f1 < 0.66);
+
+    assert!(corpus_chrf(&["a", "b"], &[vec!["a"]], &config).is_err());
+}
+proptest! {
+    #[test]
+    fn test_chrf_short_inputs(input in "([a-c] ?){0,3}", reference in "([a-c] ?){0,3}", word_order in 0usize..4) {
+        let score = sentence_chrf(&input, &[&reference], &ChrfConfig{word_order, ..ChrfConfig::default()}).unwrap();
+        for value in [score.precision, score.recall, score.f1] {
+            prop_assert!((0.0..=1.0).contains(&value), "{:?}", score);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use approx::assert_abs_diff_eq;
+use std::collections::HashSet;
+use text_score::classification::{classification_report, multilabel_report, ClassCounts, ConfusionMatrix, Normalization};
+use text_score::commons::{MetricError, Score, ZeroDivision};
+
+#[test]
+fn test_confusion_matrix(){
+    let y_true = ["cat", "ant", "cat", "cat", "ant", "bird"];
+    let y_pred = ["ant", "ant", "cat", "cat", "ant", "cat"];
+    let matrix = ConfusionMatrix::from_labels(&y_true, &y_pred).unwrap();
+
+    // labels in order of first appearance
+    assert_eq!(&["cat", "ant", "bird"], matrix.labels());
+    assert_eq!(&[vec![2, 1, 0], vec![0, 2, 0], vec![1, 0, 0]], matrix.counts());
+    assert_eq!(6, matrix.total());
+    assert_eq!(1, matrix.count(&"bird", &"cat"));
+    assert_eq!(0, matrix.count(&"bird", &"dog"));
+
+    assert_eq!(ClassCounts{true_pos: 2, false_pos: 1, false_neg: 1, true_neg: 2}, matrix.class_counts(&"cat"));
+    assert_eq!(ClassCounts{true_pos: 2, false_pos: 1, false_neg: 0, true_neg: 3}, matrix.class_counts(&"ant"));
+    assert_eq!(ClassCounts{true_pos: 0, false_pos: 0, false_neg: 1, true_neg: 5}, matrix.class_counts(&"bird"));
+    assert_eq!(ClassCounts{true_neg: 6, ..ClassCounts::default()}, matrix.class_counts(&"dog"));
+    assert_eq!(3, matrix.class_counts(&"cat").support());
+
+    assert!(matches!(ConfusionMatrix::from_labels(&["a"], &[]), Err(MetricError::LengthMismatch{inputs: 0, references: 1})));
+}
+#[test]
+fn test_confusion_matrix_updates(){
+    let mut matrix = ConfusionMatrix::with_labels(&[0, 1, 2]);
+    assert_eq!(0, matrix.total());
+    assert_eq!(vec![vec![0; 3]; 3], matrix.counts());
+
+    matrix.update(&[2, 2], &[2, 0]).unwrap();
+    matrix.add(3, 1);
+    assert!(matrix.update(&[1, 1], &[1]).is_err());
+    assert_eq!(&[0, 1, 2, 3], matrix.labels());
+    assert_eq!(3, matrix.total());
+    assert_eq!(1, matrix.count(&2, &0));
+    assert_eq!(1, matrix.count(&3, &1));
+
+    let batched = ConfusionMatrix::from_labels(&[0, 1, 2, 3], &[0, 1, 2, 3]).unwrap();
+    let mut incremental = ConfusionMatrix::new();
+    for label in 0..4 {
+        incremental.add(label, label);
+    }
+    assert_eq!(batched, incremental);
+}
+#[test]
+fn test_confusion_matrix_normalize(){
+    let matrix = ConfusionMatrix::from_labels(&["a", "a", "a", "b"], &["a", "b", "b", "b"]).unwrap();
+
+    let rows = matrix.normalize(Normalization::Rows);
+    assert_abs_diff_eq!(1.0 / 3.0, rows[0][0], epsilon = 1e-6);
+    assert_abs_diff_eq!(2.0 / 3.0, rows[0][1], epsilon = 1e-6);
+    assert_eq!(vec![0.0, 1.0], rows[1]);
+
+    let columns = matrix.normalize(Normalization::Columns);
+    assert_eq!(vec![vec![1.0, 2.0 / 3.0], vec![0.0, 1.0 / 3.0]], columns);
+
+    let all = matrix.normalize(Normalization::All);
+    assert_eq!(vec![vec![0.25, 0.5], vec![0.0, 0.25]], all);
+
+    // a label only predicted has an empty row
+    let matrix = ConfusionMatrix::from_labels(&["a"], &["b"]).unwrap();
+    assert_eq!(vec![vec![0.0, 1.0], vec![0.0, 0.0]], matrix.normalize(Normalization::Rows));
+}
+#[test]
+fn test_confusion_matrix_display(){
+    let matrix = ConfusionMatrix::from_labels(&["cat", "dog", "dog"], &["cat", "cat", "dog"]).unwrap();
+    assert_eq!("true\\pred cat dog\n      cat   1   0\n      dog   1   1", matrix.to_string());
+
+    assert_eq!("true\\pred", ConfusionMatrix::<u8>::new().to_string());
+}
+#[test]
+fn test_classification_report(){
+    // example of the scikit-learn documentation
+    let report = classification_report(&[0, 1, 2, 2, 2], &[0, 0, 2, 2, 1]).unwrap();
+    let class = |label: i32| report.class(&label).unwrap();
+    assert_eq!((0.5, 1.0, 1), (class(0).score.precision, class(0).score.recall, class(0).support));
+    assert_abs_diff_eq!(2.0 / 3.0, class(0).score.f1, epsilon = 1e-6);
+    assert_eq!(Score{precision: 0.0, recall: 0.0, f1: 0.0, beta: 1.0}, class(1).score);
+    assert_eq!((1.0, 0.8, 3), (class(2).score.precision, class(2).score.f1, class(2).support));
+    assert!(report.class(&3).is_none());
+
+    assert_eq!(0.6, report.accuracy);
+    assert_eq!(5, report.support);
+    assert_abs_diff_eq!(0.6, report.micro_avg.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.6, report.micro_avg.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.6, report.micro_avg.f1, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5, report.macro_avg.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(5.0 / 9.0, report.macro_avg.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!((2.0 / 3.0 + 0.8) / 3.0, report.macro_avg.f1, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.7, report.weighted_avg.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.6, report.weighted_avg.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!((2.0 / 3.0 + 3.0 * 0.8) / 5.0, report.weighted_avg.f1, epsilon = 1e-6);
+
+    let expected = "              precision    recall  f1-score   support
+
+           0       0.50      1.00      0.67         1
+           1       0.00      0.00      0.00         1
+           2       1.00      0.67      0.80         3
+
+    accuracy                           0.60         5
+   macro avg       0.50      0.56      0.49         5
+weighted avg       0.70      0.60      0.61         5";
+    assert_eq!(expected, report.to_string());
+
+    assert!(classification_report(&[0, 1], &[0]).is_err());
+}
+#[test]
+fn test_classification_report_zero_division(){
+    // "c" is predicted but never true, "b" is true but never predicted
+    let matrix = ConfusionMatrix::from_labels(&["a", "b"], &["a", "c"]).unwrap();
+    let report = matrix.report(ZeroDivision::One).unwrap();
+    assert_eq!(Score{precision: 1.0, recall: 0.0, f1: 0.0, beta: 1.0}, report.class(&"b").unwrap().score);
+    assert_eq!(Score{precision: 0.0, recall: 1.0, f1: 0.0, beta: 1.0}, report.class(&"c").unwrap().score);
+    assert_eq!(0, report.class(&"c").unwrap().support);
+    assert!(matches!(matrix.report(ZeroDivision::Error), Err(MetricError::ZeroDivision)));
+
+    // labels given in advance but never seen
+    let report = ConfusionMatrix::with_labels(&["a", "b"]).report(ZeroDivision::Zero).unwrap();
+    assert_eq!(0, report.support);
+    assert_eq!(0.0, report.accuracy);
+    assert_eq!(0.0, report.weighted_avg.f1);
+}
+fn label_sets(sets: &[&[&'static str]]) -> Vec<HashSet<&'static str>> {
+    sets.iter().map(|set| set.iter().copied().collect()).collect()
+}
+#[test]
+fn test_multilabel_report(){
+    let y_true = label_sets(&[&["a", "b"], &["c"], &["a"], &[]]);
+    let y_pred = label_sets(&[&["a"], &["c", "b"], &[], &[]]);
+    let report = multilabel_report(&y_true, &y_pred, ZeroDivision::Zero).unwrap();
+
+    assert_eq!(4, report.n_samples);
+    assert_eq!(0.25, report.hamming_loss);
+    assert_eq!(0.25, report.subset_accuracy);
+    assert_eq!(0.25, report.jaccard);
+
+    // per sample: (1, 1/2, 2/3), (1/2, 1, 2/3), (0, 0, 0), (0, 0, 0)
+    assert_abs_diff_eq!(0.375, report.samples_avg.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.375, report.samples_avg.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(1.0 / 3.0, report.samples_avg.f1, epsilon = 1e-6);
+
+    // per label: a (1, 1/2, 2/3), b (0, 0, 0), c (1, 1, 1)
+    let a = report.label(&"a").unwrap();
+    assert_eq!((1.0, 0.5, 2), (a.score.precision, a.score.recall, a.support));
+    assert_eq!(0.0, report.label(&"b").unwrap().score.f1);
+    assert_eq!(1.0, report.label(&"c").unwrap().score.f1);
+    assert!(report.label(&"d").is_none());
+
+    assert_abs_diff_eq!(2.0 / 3.0, report.micro_avg.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5, report.micro_avg.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(4.0 / 7.0, report.micro_avg.f1, epsilon = 1e-6);
+    assert_abs_diff_eq!(2.0 / 3.0, report.macro_avg.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5, report.macro_avg.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(5.0 / 9.0, report.macro_avg.f1, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.75, report.weighted_avg.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(7.0 / 12.0, report.weighted_avg.f1, epsilon = 1e-6);
+}
+#[test]
+fn test_multilabel_report_zero_division(){
+    let y_true = label_sets(&[&["a", "b"], &["c"], &["a"], &[]]);
+    let y_pred = label_sets(&[&["a"], &["c", "b"], &[], &[]]);
+
+    // the third sample has no predicted label, the last one no label at all
+    let report = multilabel_report(&y_true, &y_pred, ZeroDivision::One).unwrap();
+    assert_abs_diff_eq!(0.875, report.samples_avg.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.625, report.samples_avg.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5, report.jaccard, epsilon = 1e-6);
+    assert!(matches!(multilabel_report(&y_true, &y_pred, ZeroDivision::Error), Err(MetricError::ZeroDivision)));
+
+    assert!(matches!(multilabel_report(&y_true, &y_pred[..1], ZeroDivision::Zero), Err(MetricError::LengthMismatch{inputs: 1, references: 4})));
+    let report = multilabel_report::<&str>(&[], &[], ZeroDivision::Zero).unwrap();
+    assert_eq!((0, 0.0), (report.n_samples, report.subset_accuracy));
+}
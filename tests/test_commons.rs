@@ -1,4 +1,7 @@
-use metrics_rs::commons::{f1, precision, recall};
+use approx::assert_abs_diff_eq;
+use text_score::commons::{f1, f1_with_zero_division, f_beta, f_beta_with_zero_division, precision, precision_with_zero_division, recall, recall_with_zero_division, MetricError, ZeroDivision};
+use text_score::meteor::SynonymTable;
+use text_score::tokenizer::RegexTokenizer;
 
 #[test]
 fn test_precision(){
@@ -16,5 +19,46 @@ fn test_f1(){
     assert_eq!(1.0, f1(1.0, 1.0));
     assert_eq!(0.0, f1(0.0, 1.0));
     assert_eq!(0.0, f1(1.0, 0.0));
+    assert_eq!(0.0, f1(0.0, 0.0));
 
-}
\ No newline at end of file
+}
+#[test]
+fn test_metric_error(){
+    assert_eq!("n-gram order should be >= 1, got 0", MetricError::InvalidN(0).to_string());
+    assert_eq!(
+        "inputs and references should have the same length, got 2 and 1",
+        MetricError::LengthMismatch{inputs: 2, references: 1}.to_string()
+    );
+    assert_eq!("confidence_interval should be in (0, 1), got 1.5", MetricError::InvalidProbability{name: "confidence_interval", value: 1.5}.to_string());
+
+    // errors of the dependencies are wrapped
+    assert!(matches!(RegexTokenizer::new("("), Err(MetricError::InvalidPattern(_))));
+    assert!(matches!(SynonymTable::from_file("no/such/file.txt"), Err(MetricError::Io(_))));
+}
+#[test]
+fn test_zero_division(){
+    assert_eq!(0.0, precision(0, 0));
+    assert_eq!(0.0, recall(0, 0));
+    assert_eq!(0.0, f1(0.0, 0.0));
+
+    assert_eq!(1.0, precision_with_zero_division(0, 0, ZeroDivision::One).unwrap());
+    assert_eq!(1.0, recall_with_zero_division(0, 0, ZeroDivision::One).unwrap());
+    assert_eq!(1.0, f1_with_zero_division(0.0, 0.0, ZeroDivision::One).unwrap());
+    assert!(precision_with_zero_division(0, 0, ZeroDivision::NaN).unwrap().is_nan());
+    assert!(matches!(recall_with_zero_division(0, 0, ZeroDivision::Error), Err(MetricError::ZeroDivision)));
+
+    // the policy only applies to divisions by zero
+    assert_eq!(2.0/3.0, precision_with_zero_division(10, 5, ZeroDivision::Error).unwrap());
+    assert_eq!(0.0, recall_with_zero_division(0, 5, ZeroDivision::One).unwrap());
+    assert_eq!(ZeroDivision::Zero, ZeroDivision::default());
+}
+#[test]
+fn test_f_beta(){
+    assert_eq!(f1(0.5, 0.25), f_beta(0.5, 0.25, 1.0));
+    // (1 + 4) * 0.5 * 0.25 / (4 * 0.5 + 0.25)
+    assert_abs_diff_eq!(0.625 / 2.25, f_beta(0.5, 0.25, 2.0), epsilon = 1e-6);
+    // beta 0 is precision
+    assert_eq!(0.5, f_beta(0.5, 0.25, 0.0));
+    assert_eq!(0.0, f_beta(0.0, 0.0, 2.0));
+    assert_eq!(1.0, f_beta_with_zero_division(0.0, 0.0, 2.0, ZeroDivision::One).unwrap());
+}
@@ -0,0 +1,71 @@
+use approx::assert_abs_diff_eq;
+use text_score::edit_distance::{cer, corpus_cer, corpus_wer, levenshtein_alignment, wer, EditOperation};
+
+#[test]
+fn test_levenshtein_alignment(){
+    use EditOperation::*;
+
+    assert_eq!(vec![Hit, Hit, Substitution, Hit, Deletion, Hit], levenshtein_alignment(&["the", "cat", "sit", "on", "mat"], &["the", "cat", "sat", "on", "the", "mat"]));
+    assert_eq!(vec![Insertion, Hit], levenshtein_alignment(&["oh", "hello"], &["hello"]));
+    assert_eq!(vec![Deletion, Deletion], levenshtein_alignment(&[], &["a", "b"]));
+    assert_eq!(vec![Insertion], levenshtein_alignment(&["a"], &[]));
+    assert!(levenshtein_alignment(&[], &[]).is_empty());
+
+    // kitten -> sitting: 2 substitutions and 1 deletion
+    let kitten: Vec<&str> = "k i t t e n".split_whitespace().collect();
+    let sitting: Vec<&str> = "s i t t i n g".split_whitespace().collect();
+    let operations = levenshtein_alignment(&kitten, &sitting);
+    assert_eq!(2, operations.iter().filter(|&&o| o == Substitution).count());
+    assert_eq!(1, operations.iter().filter(|&&o| o == Deletion).count());
+    assert_eq!(4, operations.iter().filter(|&&o| o == Hit).count());
+}
+#[test]
+fn test_wer(){
+    // identical: 0.0
+    let result = wer("this is identical case.", "this is identical case.");
+    assert_eq!(0.0, result.error_rate);
+    assert_eq!(4, result.hits);
+
+    let result = wer("the cat sit on mat", "the cat sat on the mat");
+    assert_abs_diff_eq!(2.0 / 6.0, result.error_rate, epsilon = 1e-6);
+    assert_eq!((4, 1, 1, 0), (result.hits, result.substitutions, result.deletions, result.insertions));
+    assert_eq!(6, result.reference_len);
+
+    // insertions can make the rate greater than 1
+    let result = wer("oh well hello there", "hi");
+    assert_abs_diff_eq!(4.0, result.error_rate, epsilon = 1e-6);
+    assert_eq!((1, 3), (result.substitutions, result.insertions));
+
+    // empty input: every reference word is deleted
+    let result = wer("", "hello world");
+    assert_eq!(1.0, result.error_rate);
+    assert_eq!(2, result.deletions);
+}
+#[test]
+fn test_cer(){
+    let result = cer("kitten", "sitting");
+    assert_abs_diff_eq!(3.0 / 7.0, result.error_rate, epsilon = 1e-6);
+
+    // whitespace is collapsed into a single space
+    let result = cer("  a   b ", "a b");
+    assert_eq!(0.0, result.error_rate);
+    assert_eq!(3, result.reference_len);
+
+    // multi-byte characters count as one
+    let result = cer("café", "cafe");
+    assert_abs_diff_eq!(0.25, result.error_rate, epsilon = 1e-6);
+}
+#[test]
+fn test_corpus_wer_cer(){
+    // (1 deletion + 1 deletion) / (4 + 2)
+    let result = corpus_wer(&["the cat sat", "hello"], &["the cat sat down", "hello world"]).unwrap();
+    assert_abs_diff_eq!(2.0 / 6.0, result.error_rate, epsilon = 1e-6);
+    assert_eq!(2, result.deletions);
+    assert_eq!(6, result.reference_len);
+
+    let result = corpus_cer(&["kitten", "abc"], &["sitting", "abc"]).unwrap();
+    assert_abs_diff_eq!(3.0 / 10.0, result.error_rate, epsilon = 1e-6);
+
+    assert!(corpus_wer(&["a", "b"], &["a"]).is_err());
+    assert!(corpus_cer(&["a"], &[]).is_err());
+}
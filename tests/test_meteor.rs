@@ -0,0 +1,75 @@
+use std::fs;
+use approx::assert_abs_diff_eq;
+use text_score::meteor::{MeteorScorer, SynonymSource, SynonymTable};
+
+#[test]
+fn test_synonym_table(){
+    let mut synonyms = SynonymTable::parse("# comment\nbig large\n\nlarge great");
+    assert!(synonyms.are_synonyms("big", "large"));
+    assert!(synonyms.are_synonyms("large", "great"));
+    assert!(!synonyms.are_synonyms("big", "great"));
+    assert!(!synonyms.are_synonyms("big", "unknown"));
+
+    synonyms.add_group(&["big", "great"]);
+    assert!(synonyms.are_synonyms("big", "great"));
+
+    let path = std::env::temp_dir().join("text_score_test_synonyms.txt");
+    fs::write(&path, "sat seated\n").unwrap();
+    let synonyms = SynonymTable::from_file(&path).unwrap();
+    assert!(synonyms.are_synonyms("seated", "sat"));
+    fs::remove_file(&path).unwrap();
+
+    assert!(SynonymTable::from_file("no/such/file.txt").is_err());
+}
+#[test]
+fn test_meteor_exact(){
+    let scorer = MeteorScorer::new();
+
+    // identical: a single chunk
+    let score = scorer.score("the cat sat on the mat", "the cat sat on the mat");
+    assert_eq!((6, 1), (score.matches, score.chunks));
+    assert_abs_diff_eq!(1.0 - 0.5 / 216.0, score.score, epsilon = 1e-6);
+
+    // "the cat" and "on the mat" should be aligned as whole chunks: 3 chunks
+    let score = scorer.score("the cat sat on the mat", "on the mat sat the cat");
+    assert_eq!((6, 3), (score.matches, score.chunks));
+    assert_abs_diff_eq!(1.0 - 0.5 * 0.125, score.score, epsilon = 1e-6);
+
+    // p=1, r=1/3, fmean = 10pr / (r + 9p)
+    let score = scorer.score("the cat", "the cat sat on the mat");
+    assert_abs_diff_eq!(1.0, score.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(1.0 / 3.0, score.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(10.0 / 28.0, score.fmean, epsilon = 1e-6);
+    assert_abs_diff_eq!(10.0 / 28.0 * (1.0 - 0.5 / 8.0), score.score, epsilon = 1e-6);
+
+    // no match: 0.0
+    assert_eq!(0.0, scorer.score("a b c", "d e f").score);
+    assert_eq!(0.0, scorer.score("", "d e f").score);
+}
+#[test]
+fn test_meteor_stages(){
+    // without stemming, "cats" does not match and the alignment is split into 2 chunks
+    let score = MeteorScorer::new().score("the cats sat", "the cat sat");
+    assert_eq!((2, 2), (score.matches, score.chunks));
+    assert_abs_diff_eq!(1.0 / 3.0, score.score, epsilon = 1e-6);
+
+    let scorer = MeteorScorer::new().with_stemmer(|word: &str| word.trim_end_matches('s').to_string());
+    let score = scorer.score("the cats sat", "the cat sat");
+    assert_eq!((3, 1), (score.matches, score.chunks));
+    assert_abs_diff_eq!(1.0 - 0.5 / 27.0, score.score, epsilon = 1e-6);
+
+    let scorer = scorer.with_synonyms(SynonymTable::parse("sat seated"));
+    let score = scorer.score("the cats seated", "the cat sat");
+    assert_eq!((3, 1), (score.matches, score.chunks));
+
+    // exact matches take precedence over stems and synonyms
+    let scorer = MeteorScorer::new().with_synonyms(SynonymTable::parse("big large"));
+    let score = scorer.score("large big", "big");
+    assert_eq!(1, score.matches);
+    assert_abs_diff_eq!(0.5, score.precision, epsilon = 1e-6);
+
+    // custom parameters
+    let mut scorer = MeteorScorer::new();
+    scorer.gamma = 0.0;
+    assert_abs_diff_eq!(1.0, scorer.score("the cat sat on the mat", "on the mat sat the cat").score, epsilon = 1e-6);
+}
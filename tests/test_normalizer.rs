@@ -0,0 +1,72 @@
+use text_score::normalizer::{Normalizer, UnicodeForm};
+use text_score::rouge::{rouge_n, rouge_n_with_tokenizer};
+use text_score::bleu::{sentence_bleu, Smoothing, DEFAULT_MAX_ORDER};
+use text_score::chrf::{sentence_chrf, sentence_chrf_with_tokenizer, ChrfConfig};
+use text_score::tokenizer::{Tokenizer, WhitespaceTokenizer};
+
+#[test]
+fn test_default_normalizer(){
+    let normalizer = Normalizer::new();
+    assert_eq!(" Café,  42 ", normalizer.normalize(" Café,  42 "));
+    assert_eq!("lc:no|form:none|accents:keep|punct:keep|ws:keep|digits:keep", normalizer.signature());
+}
+#[test]
+fn test_normalization_steps(){
+    let lowercase = Normalizer{lowercase: true, ..Normalizer::default()};
+    assert_eq!("hello ÿ é", lowercase.normalize("HeLLO Ÿ É"));
+
+    // "e" + combining acute accent
+    let nfc = Normalizer{unicode_form: Some(UnicodeForm::Nfc), ..Normalizer::default()};
+    assert_eq!("caf\u{e9}", nfc.normalize("cafe\u{301}"));
+    let nfkc = Normalizer{unicode_form: Some(UnicodeForm::Nfkc), ..Normalizer::default()};
+    assert_eq!("fi ABC", nfkc.normalize("\u{fb01} ＡＢＣ"));
+    // the uppercase "H" only appears with NFKC, so it is lowercased afterwards
+    let nfkc_lowercase = Normalizer{unicode_form: Some(UnicodeForm::Nfkc), lowercase: true, ..Normalizer::default()};
+    assert_eq!("hello", nfkc_lowercase.normalize("\u{210c}ello"));
+
+    let accents = Normalizer{remove_accents: true, ..Normalizer::default()};
+    assert_eq!("Creme brulee naive", accents.normalize("Crème brûlée naïve"));
+
+    let punctuation = Normalizer{remove_punctuation: true, ..Normalizer::default()};
+    assert_eq!("dont stop  now", punctuation.normalize("don't stop -- now!"));
+    // combining marks of decomposed texts are not punctuation
+    assert_eq!("cafe\u{301}", punctuation.normalize("cafe\u{301}!"));
+
+    let whitespace = Normalizer{collapse_whitespace: true, ..Normalizer::default()};
+    assert_eq!("a b c", whitespace.normalize("\t a \n b  c "));
+
+    let digits = Normalizer{mask_digits: Some('#'), ..Normalizer::default()};
+    assert_eq!("room ### on ##/##", digits.normalize("room 101 on 12/31"));
+}
+#[test]
+fn test_normalizing_tokenizer(){
+    let normalizer = Normalizer{lowercase: true, remove_punctuation: true, ..Normalizer::default()};
+    let tokenizer = normalizer.tokenizer(WhitespaceTokenizer);
+    assert_eq!(vec!["hello", "world"], tokenizer.tokenize("Hello, World!"));
+    assert_eq!(normalizer, tokenizer.normalizer);
+
+    let score = rouge_n_with_tokenizer("The cat sat.", "the cat sat", 2, &tokenizer).unwrap();
+    assert_eq!(1.0, score.f1);
+}
+#[test]
+fn test_normalized_score(){
+    let normalizer = Normalizer{lowercase: true, remove_punctuation: true, collapse_whitespace: true, ..Normalizer::default()};
+
+    let result = normalizer.score("This is a case.", "this is a case", |i, r| rouge_n(i, r, 1)).unwrap();
+    assert_eq!(1.0, result.score.f1);
+    assert_eq!(normalizer, result.normalizer);
+    assert_eq!(normalizer.signature(), result.normalizer.to_string());
+
+    let result = normalizer.score("The cat is on the mat!", "the cat is on the mat", |i, r| sentence_bleu(i, &[r], DEFAULT_MAX_ORDER, Smoothing::None)).unwrap();
+    assert_eq!(1.0, result.score.score);
+
+    // the character n-grams of chrF are only normalized by scoring normalized texts, not by the tokenizer
+    let config = ChrfConfig::chrf_plus_plus();
+    let result = normalizer.score("The Cat!", "the cat", |i, r| sentence_chrf(i, &[r], &config)).unwrap();
+    assert_eq!(1.0, result.score.f1);
+    let score = sentence_chrf_with_tokenizer("The Cat!", &["the cat"], &config, &normalizer.tokenizer(WhitespaceTokenizer)).unwrap();
+    assert!(score.f1 < 1.0);
+
+    // errors of the metric are passed through
+    assert!(normalizer.score("a", "a", |i, r| rouge_n(i, r, 0)).is_err());
+}
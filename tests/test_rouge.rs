@@ -1,17 +1,52 @@
 use approx::assert_abs_diff_eq;
-use metrics_rs::rouge::{create_ngrams, rouge_n};
-use metrics_rs::commons::{f1, precision, recall};
+use proptest::prelude::*;
+use text_score::rouge::{multi_reference_score, ngram_based_score, ngram_based_score_with_zero_division, ZeroDivision, Aggregation, create_ngrams, create_skip_bigrams, lcs_indices, lcs_table, rouge_l, rouge_l_with_tokenizer, rouge_lsum, rouge_lsum_with_tokenizer, rouge_n, rouge_n_with_tokenizer, rouge_s, rouge_su, rouge_w, weighted_lcs, Score, DEFAULT_ROUGE_W_WEIGHT, RougeScorer, RougeType};
+use text_score::commons::{f1, MetricError};
+use text_score::stemmer::NltkPorterStemmer;
+use text_score::tokenizer::{RougeTokenizer, WhitespaceTokenizer};
 
 #[test]
 fn test_create_ngram(){
     let tokens = "I want to build awesome rust codes".split_whitespace().collect();
     let n = 2;
 
-    let mut ngrams = create_ngrams(tokens, n);
+    let ngrams = create_ngrams(tokens, n);
 
     for (key, value) in ngrams.iter() {
         assert_eq!(ngrams.get(key).unwrap(), value);
     }
+
+    // fewer tokens than n: no n-grams
+    assert!(create_ngrams(vec!["hi"], 2).is_empty());
+    assert!(create_ngrams(vec![], 1).is_empty());
+    let score = rouge_n("hi", "hello there", 2).unwrap();
+    assert_eq!(Score{precision:0.0, recall:0.0, f1:0.0, beta:1.0}, score);
+}
+#[test]
+fn test_ngram_based_score_zero_division(){
+    let empty = || create_ngrams(vec![], 1);
+    let target = || create_ngrams(vec!["a", "b"], 1);
+
+    let score = ngram_based_score_with_zero_division(empty(), target(), 1.0, ZeroDivision::One).unwrap();
+    assert_eq!(Score{precision:1.0, recall:0.0, f1:0.0, beta:1.0}, score);
+    let score = ngram_based_score_with_zero_division(target(), empty(), 1.0, ZeroDivision::One).unwrap();
+    assert_eq!(Score{precision:0.0, recall:1.0, f1:0.0, beta:1.0}, score);
+    let score = ngram_based_score_with_zero_division(empty(), empty(), 1.0, ZeroDivision::One).unwrap();
+    assert_eq!(Score{precision:1.0, recall:1.0, f1:1.0, beta:1.0}, score);
+    let score = ngram_based_score_with_zero_division(empty(), empty(), 1.0, ZeroDivision::NaN).unwrap();
+    assert!(score.precision.is_nan() && score.recall.is_nan() && score.f1.is_nan());
+    assert!(matches!(ngram_based_score_with_zero_division(empty(), target(), 1.0, ZeroDivision::Error), Err(MetricError::ZeroDivision)));
+
+    // recall-oriented: beta 2
+    let score = ngram_based_score(create_ngrams(vec!["a", "c", "d", "e"], 1), target(), 2.0);
+    assert_eq!((0.25, 0.5, 2.0), (score.precision, score.recall, score.beta));
+    assert_abs_diff_eq!(5.0 * 0.125 / 1.5, score.f1, epsilon = 1e-6);
+    let score = ngram_based_score_with_zero_division(empty(), target(), 2.0, ZeroDivision::One).unwrap();
+    assert_eq!(Score{precision:1.0, recall:0.0, f1:0.0, beta:2.0}, score);
+
+    // same as ngram_based_score without division by zero
+    let score = ngram_based_score_with_zero_division(create_ngrams(vec!["a", "c"], 1), target(), 1.0, ZeroDivision::Error).unwrap();
+    assert_eq!(ngram_based_score(create_ngrams(vec!["a", "c"], 1), target(), 1.0), score);
 }
 #[test]
 fn test_rouge1() {
@@ -32,6 +67,255 @@ fn test_rouge1() {
     assert_abs_diff_eq!(f1(1.0, 5.0/6.0),  score.f1, epsilon = 1e-3);
 
     let result = rouge_n("it is what it is.", "it is really what it is.", 0);
-    assert!(result.is_err());
+    assert!(matches!(result, Err(MetricError::InvalidN(0))));
+
+}
+#[test]
+fn test_lcs_table(){
+    let a: Vec<&str> = "A B C B D A B".split_whitespace().collect();
+    let b: Vec<&str> = "B D C A B A".split_whitespace().collect();
+
+    let table = lcs_table(&a, &b);
+    assert_eq!(4, table[a.len()][b.len()]);
+    assert_eq!(0, table[0][b.len()]);
+}
+#[test]
+fn test_rouge_l() {
+    // identical: 1.0
+    let score = rouge_l("this is identical case.", "this is identical case.");
+    assert_eq!(1.0, score.f1);
+
+    // example from Lin (2004): LCS = 3 ("police the gunman")
+    let score = rouge_l("police killed the gunman", "police kill the gunman");
+    assert_abs_diff_eq!(0.75, score.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.75, score.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.75, score.f1, epsilon = 1e-6);
+
+    // order matters: LCS = 2 ("the gunman")
+    let score = rouge_l("the gunman police killed", "police kill the gunman");
+    assert_abs_diff_eq!(0.5, score.f1, epsilon = 1e-6);
+
+    // not consecutive: p=3/3, r=3/5
+    let score = rouge_l("a c e", "a b c d e");
+    assert_abs_diff_eq!(1.0, score.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.6, score.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(f1(1.0, 0.6), score.f1, epsilon = 1e-6);
+}
+#[test]
+fn test_lcs_indices(){
+    let reference: Vec<&str> = "w1 w2 w3 w4 w5".split_whitespace().collect();
+
+    assert_eq!(vec![0, 1], lcs_indices(&reference, &["w1", "w2", "w6", "w7", "w8"]));
+    assert_eq!(vec![0, 2, 4], lcs_indices(&reference, &["w1", "w3", "w8", "w9", "w5"]));
+    assert!(lcs_indices(&reference, &[]).is_empty());
+}
+#[test]
+fn test_rouge_lsum() {
+    // test case from google-research rouge_scorer_test.py: union LCS = "w1 w2 w3 w5"
+    let score = rouge_lsum("w1 w2 w6 w7 w8\nw1 w3 w8 w9 w5", "w1 w2 w3 w4 w5");
+    assert_abs_diff_eq!(0.4, score.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.8, score.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5333, score.f1, epsilon = 1e-4);
+
+    // empty lines are ignored
+    let score = rouge_lsum("w1 w2 w6 w7 w8\n\nw1 w3 w8 w9 w5\n", "w1 w2 w3 w4 w5");
+    assert_abs_diff_eq!(0.5333, score.f1, epsilon = 1e-4);
+
+    // a single sentence is the same as rouge-l
+    let score = rouge_lsum("police killed the gunman", "police kill the gunman");
+    assert_abs_diff_eq!(rouge_l("police killed the gunman", "police kill the gunman").f1, score.f1, epsilon = 1e-6);
+
+    // tokens are hit only as many times as they appear
+    let score = rouge_lsum("a b\na b", "a b");
+    assert_abs_diff_eq!(0.5, score.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(1.0, score.recall, epsilon = 1e-6);
+
+    // empty input
+    let score = rouge_lsum("", "w1 w2 w3 w4 w5");
+    assert_eq!(0.0, score.f1);
+}
+#[test]
+fn test_create_skip_bigrams(){
+    let tokens: Vec<&str> = "police killed the gunman".split_whitespace().collect();
+
+    let skip_bigrams = create_skip_bigrams(tokens.clone(), None);
+    assert_eq!(6, skip_bigrams.len());
+    assert_eq!(Some(&1), skip_bigrams.get(&vec!["police", "gunman"]));
+
+    let skip_bigrams = create_skip_bigrams(tokens.clone(), Some(1));
+    assert_eq!(5, skip_bigrams.len());
+    assert_eq!(None, skip_bigrams.get(&vec!["police", "gunman"]));
+
+    // without gaps, same as bigrams
+    assert_eq!(create_ngrams(tokens.clone(), 2), create_skip_bigrams(tokens.clone(), Some(0)));
+    // any distance, same as no limit
+    assert_eq!(create_skip_bigrams(tokens.clone(), None), create_skip_bigrams(tokens, Some(usize::MAX)));
+
+    // duplicated pairs are counted
+    let skip_bigrams = create_skip_bigrams(vec!["a", "b", "a", "b"], None);
+    assert_eq!(Some(&3), skip_bigrams.get(&vec!["a", "b"]));
+    assert!(create_skip_bigrams(vec![], None).is_empty());
+}
+#[test]
+fn test_rouge_s() {
+    // identical: 1.0
+    let score = rouge_s("this is identical case.", "this is identical case.", None);
+    assert_eq!(1.0, score.f1);
+
+    // examples from Lin (2004), S1 as reference, C(4,2) = 6 skip-bigrams each
+    let reference = "police killed the gunman";
+    let score = rouge_s("police kill the gunman", reference, None);
+    assert_abs_diff_eq!(0.5, score.f1, epsilon = 1e-6);
+    let score = rouge_s("the gunman kill police", reference, None);
+    assert_abs_diff_eq!(1.0 / 6.0, score.f1, epsilon = 1e-6);
+    let score = rouge_s("the gunman police killed", reference, None);
+    assert_abs_diff_eq!(2.0 / 6.0, score.f1, epsilon = 1e-6);
 
-}
\ No newline at end of file
+    // "police gunman" is out of reach with max_skip 1
+    let score = rouge_s("police kill the gunman", reference, Some(1));
+    assert_abs_diff_eq!(0.4, score.f1, epsilon = 1e-6);
+}
+#[test]
+fn test_rouge_su() {
+    let reference = "police killed the gunman";
+
+    // reversed sentence has no skip-bigram in common, but all unigrams match
+    let score = rouge_s("gunman the killed police", reference, None);
+    assert_eq!(0.0, score.precision);
+    let score = rouge_su("gunman the killed police", reference, None);
+    assert_abs_diff_eq!(0.4, score.f1, epsilon = 1e-6);
+
+    // 3 skip-bigrams + 3 unigrams out of 6 + 4
+    let score = rouge_su("police kill the gunman", reference, None);
+    assert_abs_diff_eq!(0.6, score.f1, epsilon = 1e-6);
+}
+#[test]
+fn test_weighted_lcs(){
+    // examples from Lin (2004) with f(k) = k^2
+    let x: Vec<&str> = "A B C D E F G".split_whitespace().collect();
+    let y1: Vec<&str> = "A B C D H I K".split_whitespace().collect();
+    let y2: Vec<&str> = "A H B K C I D".split_whitespace().collect();
+
+    assert_abs_diff_eq!(16.0, weighted_lcs(&x, &y1, 2.0), epsilon = 1e-6);
+    assert_abs_diff_eq!(4.0, weighted_lcs(&x, &y2, 2.0), epsilon = 1e-6);
+
+    // weight 1 is the plain LCS
+    assert_abs_diff_eq!(4.0, weighted_lcs(&x, &y2, 1.0), epsilon = 1e-6);
+    assert_eq!(0.0, weighted_lcs(&x, &[], 2.0));
+}
+#[test]
+fn test_rouge_w() {
+    let reference = "A B C D E F G";
+
+    // identical: 1.0
+    let score = rouge_w(reference, reference, DEFAULT_ROUGE_W_WEIGHT).unwrap();
+    assert_abs_diff_eq!(1.0, score.f1, epsilon = 1e-6);
+
+    // examples from Lin (2004): recall = sqrt(16/49) vs sqrt(4/49)
+    let score = rouge_w("A B C D H I K", reference, 2.0).unwrap();
+    assert_abs_diff_eq!(4.0 / 7.0, score.recall, epsilon = 1e-6);
+    let score = rouge_w("A H B K C I D", reference, 2.0).unwrap();
+    assert_abs_diff_eq!(2.0 / 7.0, score.recall, epsilon = 1e-6);
+
+    // same LCS, but consecutive matches are rewarded
+    let consecutive = rouge_w("A B C D H I K", reference, DEFAULT_ROUGE_W_WEIGHT).unwrap();
+    let scattered = rouge_w("A H B K C I D", reference, DEFAULT_ROUGE_W_WEIGHT).unwrap();
+    assert_abs_diff_eq!(rouge_l("A B C D H I K", reference).f1, rouge_l("A H B K C I D", reference).f1, epsilon = 1e-6);
+    assert!(consecutive.f1 > scattered.f1);
+
+    // weight 1 is the same as rouge-l
+    let score = rouge_w("police killed the gunman", "police kill the gunman", 1.0).unwrap();
+    assert_abs_diff_eq!(rouge_l("police killed the gunman", "police kill the gunman").f1, score.f1, epsilon = 1e-6);
+
+    let result = rouge_w("A B C D H I K", reference, 0.5);
+    assert!(matches!(result, Err(MetricError::InvalidWeight(_))));
+    let result = rouge_w("a b", "a b", f32::NAN);
+    assert!(matches!(result, Err(MetricError::InvalidWeight(w)) if w.is_nan()));
+}
+#[test]
+fn test_multi_reference_score() {
+    let references = ["wow this is identical case.", "this is identical case.", "nothing in common"];
+
+    // best-of picks the identical reference
+    let result = multi_reference_score("this is identical case.", &references, Aggregation::Max, |i, r| rouge_n(i, r, 1)).unwrap();
+    assert_eq!(1, result.best_reference);
+    assert_eq!(1.0, result.score.f1);
+
+    // average over references, p = (1 + 1 + 0) / 3, r = (4/5 + 1 + 0) / 3
+    let result = multi_reference_score("this is identical case.", &references[..2], Aggregation::Average, |i, r| rouge_n(i, r, 1)).unwrap();
+    assert_eq!(1, result.best_reference);
+    assert_abs_diff_eq!(1.0, result.score.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.9, result.score.recall, epsilon = 1e-6);
+    assert_abs_diff_eq!((f1(1.0, 0.8) + 1.0) / 2.0, result.score.f1, epsilon = 1e-6);
+
+    // works with any scorer
+    let result = multi_reference_score("police killed the gunman", &["police kill the gunman"], Aggregation::Max, |i, r| Ok(rouge_l(i, r))).unwrap();
+    assert_eq!(0, result.best_reference);
+    assert_abs_diff_eq!(0.75, result.score.f1, epsilon = 1e-6);
+
+    // errors are propagated
+    assert!(multi_reference_score("this is identical case.", &references, Aggregation::Max, |i, r| rouge_n(i, r, 0)).is_err());
+    assert!(matches!(multi_reference_score("this is identical case.", &[], Aggregation::Max, |i, r| rouge_n(i, r, 1)), Err(MetricError::EmptyInput("references"))));
+}
+#[test]
+fn test_rouge_type(){
+    assert_eq!(RougeType::RougeN(2), "rouge2".parse().unwrap());
+    assert_eq!(RougeType::RougeLsum, "rougeLsum".parse().unwrap());
+    for name in ["rouge1", "rouge9", "rougeL", "rougeLsum"] {
+        assert_eq!(name, name.parse::<RougeType>().unwrap().to_string());
+    }
+    for name in ["rouge0", "rouge10", "rougel", "rouge", "bleu"] {
+        assert!(name.parse::<RougeType>().is_err());
+    }
+    assert!(matches!(RougeScorer::new(&["rouge1", "rougeX"]), Err(MetricError::InvalidRougeType(name)) if name == "rougeX"));
+}
+#[test]
+fn test_rouge_scorer(){
+    // example of the rouge_score README, with use_stemmer=True
+    let scorer = RougeScorer::new(&["rouge1", "rougeL"]).unwrap().with_stemmer(NltkPorterStemmer);
+    let scores = scorer.score("The quick brown fox jumps over the lazy dog", "The quick brown dog jumps on the log.");
+    assert_eq!(2, scores.len());
+    assert_abs_diff_eq!(0.75, scores["rouge1"].precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(2.0 / 3.0, scores["rouge1"].recall, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.625, scores["rougeL"].precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(5.0 / 9.0, scores["rougeL"].recall, epsilon = 1e-6);
+
+    // same as the separate functions, with the target first
+    // case and punctuation are kept by the whitespace tokenizer, unlike the default one
+    let (target, prediction) = ("w1 w2 w3 w4 w5", "W1 w2 w6 w7 w8\nw1 w3, w8 w9 w5");
+    let scorer = RougeScorer::new(&["rouge1", "rouge2", "rougeL", "rougeLsum"]).unwrap().with_tokenizer(WhitespaceTokenizer);
+    let scores = scorer.score(target, prediction);
+    assert_eq!(rouge_n_with_tokenizer(prediction, target, 1, &WhitespaceTokenizer).unwrap(), scores["rouge1"]);
+    assert_eq!(rouge_n_with_tokenizer(prediction, target, 2, &WhitespaceTokenizer).unwrap(), scores["rouge2"]);
+    assert_eq!(rouge_l_with_tokenizer(prediction, target, &WhitespaceTokenizer), scores["rougeL"]);
+    assert_eq!(rouge_lsum_with_tokenizer(prediction, target, &WhitespaceTokenizer), scores["rougeLsum"]);
+    assert_ne!(rouge_lsum_with_tokenizer(prediction, target, &RougeTokenizer), scores["rougeLsum"]);
+}
+fn assert_bounded(score: Score) -> Result<(), TestCaseError> {
+    for value in [score.precision, score.recall, score.f1] {
+        prop_assert!((0.0..=1.0).contains(&value), "{:?}", score);
+    }
+    Ok(())
+}
+proptest! {
+    #[test]
+    fn test_create_ngrams_short_inputs(tokens in prop::collection::vec("[a-c]", 0..6), n in 1usize..8) {
+        let ngrams = create_ngrams(tokens.iter().map(String::as_str).collect(), n);
+        prop_assert_eq!((tokens.len() + 1).saturating_sub(n) as u32, ngrams.values().sum::<u32>());
+        prop_assert!(ngrams.keys().all(|ngram| ngram.len() == n));
+    }
+    #[test]
+    fn test_rouge_short_inputs(input in "([a-c] ?){0,4}", reference in "([a-c]\n?){0,4}", n in 1usize..6) {
+        assert_bounded(rouge_n(&input, &reference, n).unwrap())?;
+        assert_bounded(rouge_l(&input, &reference))?;
+        assert_bounded(rouge_lsum(&input, &reference))?;
+        assert_bounded(rouge_s(&input, &reference, None))?;
+        assert_bounded(rouge_su(&input, &reference, Some(1)))?;
+        assert_bounded(rouge_w(&input, &reference, DEFAULT_ROUGE_W_WEIGHT).unwrap())?;
+
+        let scorer = RougeScorer::new(&["rouge1", "rouge3", "rougeL", "rougeLsum"]).unwrap();
+        for score in scorer.score(&reference, &input).into_values() {
+            assert_bounded(score)?;
+        }
+    }
+}
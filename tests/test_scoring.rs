@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use approx::assert_abs_diff_eq;
+use text_score::commons::{MetricError, Score};
+use text_score::scoring::BootstrapAggregator;
+
+#[test]
+fn test_bootstrap_aggregator_constant(){
+    // every resample of identical scores has the same mean
+    let mut aggregator = BootstrapAggregator::new(100, 0.95, 0).unwrap();
+    for _ in 0..10 {
+        aggregator.add_score("rouge1", Score{precision: 0.5, recall: 0.25, f1: 1.0 / 3.0, beta: 1.0});
+    }
+
+    let result = aggregator.aggregate();
+    let rouge1 = result["rouge1"];
+    for score in [rouge1.mean, rouge1.low, rouge1.mid, rouge1.high] {
+        assert_abs_diff_eq!(0.5, score.precision, epsilon = 1e-6);
+        assert_abs_diff_eq!(0.25, score.recall, epsilon = 1e-6);
+        assert_abs_diff_eq!(1.0 / 3.0, score.f1, epsilon = 1e-6);
+    }
+}
+#[test]
+fn test_bootstrap_aggregator(){
+    let mut aggregator = BootstrapAggregator::new(1000, 0.95, 42).unwrap();
+    for i in 0..20 {
+        let value = i as f32 / 19.0;
+        let mut scores: HashMap<String, Score> = HashMap::new();
+        scores.insert("rouge1".to_string(), Score{precision: value, recall: value, f1: value, beta: 1.0});
+        scores.insert("rouge2".to_string(), Score{precision: 1.0, recall: 0.0, f1: 0.0, beta: 1.0});
+        aggregator.add_scores(&scores);
+    }
+
+    let result = aggregator.aggregate();
+    assert_eq!(2, result.len());
+
+    let rouge1 = result["rouge1"];
+    assert_abs_diff_eq!(0.5, rouge1.mean.f1, epsilon = 1e-6);
+    assert!(rouge1.low.f1 < rouge1.mid.f1 && rouge1.mid.f1 < rouge1.high.f1);
+    assert!(rouge1.low.f1 > 0.3 && rouge1.high.f1 < 0.7);
+    assert_abs_diff_eq!(0.5, rouge1.mid.f1, epsilon = 0.05);
+
+    // same seed, same result
+    let mut other = BootstrapAggregator::new(1000, 0.95, 42).unwrap();
+    for i in 0..20 {
+        let value = i as f32 / 19.0;
+        other.add_score("rouge1", Score{precision: value, recall: value, f1: value, beta: 1.0});
+    }
+    assert_eq!(rouge1, other.aggregate()["rouge1"]);
+}
+#[test]
+fn test_bootstrap_aggregator_invalid(){
+    assert!(matches!(BootstrapAggregator::new(0, 0.95, 0), Err(MetricError::InvalidCount("n_samples"))));
+    assert!(matches!(BootstrapAggregator::new(1000, 0.0, 0), Err(MetricError::InvalidProbability{name: "confidence_interval", ..})));
+    assert!(BootstrapAggregator::new(1000, 1.0, 0).is_err());
+    assert!(BootstrapAggregator::new(1000, 0.95, 0).unwrap().aggregate().is_empty());
+}
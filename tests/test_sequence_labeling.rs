@@ -0,0 +1,76 @@
+use approx::assert_abs_diff_eq;
+use text_score::commons::{MetricError, ZeroDivision};
+use text_score::sequence_labeling::{entity_report, get_entities, Mode, Scheme};
+
+fn spans(tags: &[&str], mode: Mode) -> Vec<(usize, usize, String)> {
+    get_entities(tags, mode).unwrap().into_iter().map(|e| (e.start, e.end, e.entity_type)).collect()
+}
+
+#[test]
+fn test_get_entities_lenient(){
+    assert_eq!(vec![(0, 2, "PER".to_string()), (3, 4, "LOC".to_string())], spans(&["B-PER", "I-PER", "O", "B-LOC"], Mode::Lenient));
+    // a chunk begins at I- after O, and ends when the type changes
+    assert_eq!(vec![(1, 2, "PER".to_string()), (2, 3, "LOC".to_string())], spans(&["O", "I-PER", "I-LOC"], Mode::Lenient));
+    // B- after I- of the same type begins a new chunk
+    assert_eq!(vec![(0, 1, "PER".to_string()), (1, 3, "PER".to_string())], spans(&["I-PER", "B-PER", "I-PER"], Mode::Lenient));
+    // BIOES and IOE tags are decoded as well
+    assert_eq!(vec![(0, 2, "PER".to_string()), (2, 3, "LOC".to_string())], spans(&["B-PER", "E-PER", "S-LOC", "O"], Mode::Lenient));
+    assert!(spans(&[], Mode::Lenient).is_empty());
+}
+#[test]
+fn test_get_entities_strict(){
+    // I- cannot begin an entity in BIO
+    assert_eq!(vec![(3, 4, "LOC".to_string())], spans(&["I-PER", "I-PER", "O", "B-LOC"], Mode::Strict(Scheme::Bio)));
+    assert_eq!(vec![(0, 2, "PER".to_string()), (2, 3, "PER".to_string())], spans(&["I-PER", "I-PER", "B-PER", "O"], Mode::Strict(Scheme::Iob1)));
+    assert_eq!(vec![(0, 2, "PER".to_string()), (2, 3, "PER".to_string())], spans(&["I-PER", "E-PER", "E-PER", "O"], Mode::Strict(Scheme::Ioe2)));
+    // IOE2 entities have to end with E-
+    assert!(spans(&["I-PER", "I-PER", "O"], Mode::Strict(Scheme::Ioe2)).is_empty());
+    assert_eq!(vec![(0, 3, "PER".to_string()), (3, 4, "PER".to_string())], spans(&["I-PER", "I-PER", "E-PER", "I-PER", "O"], Mode::Strict(Scheme::Ioe1)));
+    // BIOES entities without E- are left out
+    assert_eq!(vec![(0, 2, "PER".to_string()), (2, 3, "LOC".to_string())], spans(&["B-PER", "E-PER", "S-LOC", "B-ORG", "I-ORG", "O"], Mode::Strict(Scheme::Bioes)));
+
+    assert!(matches!(get_entities(&["B-PER", "E-PER"], Mode::Strict(Scheme::Bio)), Err(MetricError::InvalidTag(tag)) if tag == "E-PER"));
+    assert!(matches!(get_entities(&["B-"], Mode::Strict(Scheme::Bioes)), Err(MetricError::InvalidTag(_))));
+    assert!(matches!(get_entities(&["PER"], Mode::Strict(Scheme::Bio)), Err(MetricError::InvalidTag(_))));
+}
+#[test]
+fn test_entity_report(){
+    // example of the seqeval README
+    let y_true = vec![vec!["O", "O", "O", "B-MISC", "I-MISC", "I-MISC", "O"], vec!["B-PER", "I-PER", "O"]];
+    let y_pred = vec![vec!["O", "O", "B-MISC", "I-MISC", "I-MISC", "I-MISC", "O"], vec!["B-PER", "I-PER", "O"]];
+    let report = entity_report(&y_true, &y_pred, Mode::default(), ZeroDivision::Zero).unwrap();
+
+    assert_eq!(vec!["MISC", "PER"], report.types.iter().map(|c| c.label.as_str()).collect::<Vec<&str>>());
+    assert_eq!(0.0, report.entity_type("MISC").unwrap().score.f1);
+    assert_eq!(1.0, report.entity_type("PER").unwrap().score.f1);
+    assert!(report.entity_type("LOC").is_none());
+    assert_abs_diff_eq!(0.5, report.micro_avg.precision, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5, report.micro_avg.f1, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5, report.macro_avg.f1, epsilon = 1e-6);
+    assert_abs_diff_eq!(0.5, report.weighted_avg.f1, epsilon = 1e-6);
+    assert_eq!(2, report.support);
+
+    let expected = "              precision    recall  f1-score   support\n\
+                    \n        MISC       0.00      0.00      0.00         1\
+                    \n         PER       1.00      1.00      1.00         1\n\
+                    \n   micro avg       0.50      0.50      0.50         2\
+                    \n   macro avg       0.50      0.50      0.50         2\
+                    \nweighted avg       0.50      0.50      0.50         2";
+    assert_eq!(expected, report.to_string());
+}
+#[test]
+fn test_entity_report_strict(){
+    // example of the seqeval README
+    let y_true = vec![vec!["B-NP", "I-NP", "O"]];
+    let y_pred = vec![vec!["I-NP", "I-NP", "O"]];
+
+    assert_eq!(1.0, entity_report(&y_true, &y_pred, Mode::Lenient, ZeroDivision::Zero).unwrap().micro_avg.f1);
+    let report = entity_report(&y_true, &y_pred, Mode::Strict(Scheme::Bio), ZeroDivision::Zero).unwrap();
+    assert_eq!(0.0, report.micro_avg.f1);
+    assert_eq!(1, report.entity_type("NP").unwrap().support);
+    assert!(matches!(entity_report(&y_true, &y_pred, Mode::Strict(Scheme::Bio), ZeroDivision::Error), Err(MetricError::ZeroDivision)));
+
+    assert!(matches!(entity_report(&y_true, &[], Mode::Lenient, ZeroDivision::Zero), Err(MetricError::LengthMismatch{inputs: 0, references: 1})));
+    assert!(matches!(entity_report(&y_true, &[vec!["O"]], Mode::Lenient, ZeroDivision::Zero), Err(MetricError::LengthMismatch{inputs: 1, references: 3})));
+    assert!(matches!(entity_report(&y_true, &[vec!["O", "O", "S-NP"]], Mode::Strict(Scheme::Bio), ZeroDivision::Zero), Err(MetricError::InvalidTag(_))));
+}
@@ -0,0 +1,89 @@
+use std::fs;
+use text_score::stemmer::{NltkPorterStemmer, PorterStemmer, Stemmer};
+use text_score::tokenizer::{RougeTokenizer, StemmingTokenizer, Tokenizer, WhitespaceTokenizer};
+use text_score::rouge::{rouge_l_with_tokenizer, rouge_n, rouge_n_with_tokenizer};
+
+#[test]
+fn test_closure_stemmer(){
+    let strip_s = |word: &str| word.trim_end_matches('s').to_string();
+    assert_eq!("cat", strip_s.stem("cats"));
+}
+#[test]
+fn test_porter_stemmer(){
+    // examples of Porter (1980)
+    let cases = [
+        ("caresses", "caress"), ("ponies", "poni"), ("ties", "ti"), ("cats", "cat"),
+        ("feed", "feed"), ("agreed", "agre"), ("plastered", "plaster"), ("bled", "bled"),
+        ("motoring", "motor"), ("sing", "sing"), ("conflated", "conflat"), ("troubled", "troubl"),
+        ("sized", "size"), ("hopping", "hop"), ("falling", "fall"), ("filing", "file"),
+        ("happy", "happi"), ("sky", "sky"), ("relational", "relat"), ("conditional", "condit"),
+        ("vietnamization", "vietnam"), ("hopefulness", "hope"), ("sensibiliti", "sensibl"),
+        ("triplicate", "triplic"), ("electrical", "electr"), ("goodness", "good"),
+        ("revival", "reviv"), ("adjustable", "adjust"), ("adoption", "adopt"), ("communism", "commun"),
+        ("probate", "probat"), ("rate", "rate"), ("cease", "ceas"), ("controll", "control"), ("roll", "roll"),
+    ];
+    for (word, stem) in cases {
+        assert_eq!(stem, PorterStemmer.stem(word), "{}", word);
+    }
+    // departures of the reference implementation
+    assert_eq!("sensibl", PorterStemmer.stem("sensibli"));
+    assert_eq!("analog", PorterStemmer.stem("analogi"));
+    assert_eq!("as", PorterStemmer.stem("as"));
+}
+#[test]
+fn test_porter_vocabulary(){
+    // the vocabulary and the expected output of Martin Porter's reference implementation
+    let vocabulary = fs::read_to_string("tests/data/porter_vocabulary.txt").unwrap();
+    let mut n_words = 0;
+    for line in vocabulary.lines() {
+        let (word, stem) = line.split_once(' ').unwrap();
+        assert_eq!(stem, PorterStemmer.stem(word), "{}", word);
+        n_words += 1;
+    }
+    assert_eq!(23531, n_words);
+}
+#[test]
+fn test_nltk_porter_stemmer(){
+    let cases = [
+        // irregular forms
+        ("dying", "die"), ("lying", "lie"), ("skies", "sky"), ("news", "news"), ("proceed", "proceed"),
+        // "-ies" and "-ied"
+        ("dies", "die"), ("died", "die"), ("spied", "spi"), ("ponies", "poni"),
+        // final "y" only after a consonant
+        ("enjoy", "enjoy"), ("happy", "happi"),
+        // step 2
+        ("beautifully", "beauti"), ("geology", "geolog"),
+        // two letter stems
+        ("using", "use"),
+        // same as the reference implementation otherwise
+        ("running", "run"), ("generalizations", "gener"), ("hopefulness", "hope"), ("as", "as"),
+    ];
+    for (word, stem) in cases {
+        assert_eq!(stem, NltkPorterStemmer.stem(word), "{}", word);
+    }
+    assert_eq!("dy", PorterStemmer.stem("dying"));
+    assert_eq!("enjoi", PorterStemmer.stem("enjoy"));
+    assert_eq!("us", PorterStemmer.stem("using"));
+}
+#[test]
+fn test_stemming_tokenizer(){
+    let tokenizer = StemmingTokenizer::new(WhitespaceTokenizer, PorterStemmer, 0);
+    assert_eq!(vec!["run", "run", "a"], tokenizer.tokenize("running runs a"));
+
+    // rouge_score stems only tokens longer than 3 characters
+    let tokenizer = StemmingTokenizer::rouge();
+    assert_eq!(vec!["the", "cat", "ran", "was", "run"], tokenizer.tokenize("The cats ran, was running"));
+}
+#[test]
+fn test_rouge_with_stemming(){
+    let score = rouge_n("the runner was running", "the runner runs", 1).unwrap();
+    assert_eq!(0.5, score.precision);
+
+    let tokenizer = StemmingTokenizer::rouge();
+    let score = rouge_n_with_tokenizer("The runner was running.", "the runner runs", 1, &tokenizer).unwrap();
+    assert_eq!(0.75, score.precision);
+    assert_eq!(1.0, score.recall);
+
+    let score = rouge_l_with_tokenizer("Connected connections", "connecting connection", &StemmingTokenizer::new(RougeTokenizer, PorterStemmer, 0));
+    assert_eq!(1.0, score.f1);
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use text_score::rouge::{rouge_n, rouge_n_with_tokenizer};
+use text_score::stemmer::PorterStemmer;
+use text_score::stopwords::{Language, StopwordList};
+use text_score::tokenizer::{RougeTokenizer, StemmingTokenizer, Tokenizer, WhitespaceTokenizer};
+
+#[test]
+fn test_bundled_lists(){
+    let english = StopwordList::for_language(Language::English);
+    assert_eq!(570, english.len());
+    assert!(english.contains("a's") && english.contains("Would") && english.contains("zero"));
+    assert!(!english.contains("summary"));
+
+    assert!(StopwordList::for_language(Language::German).contains("und"));
+    assert!(StopwordList::for_language(Language::French).contains("les"));
+    assert!(StopwordList::for_language(Language::Spanish).contains("para"));
+    assert!(!StopwordList::for_language(Language::German).contains("the"));
+}
+#[test]
+fn test_custom_list(){
+    let mut stopwords = StopwordList::parse("# custom list\nFoo\n\n  bar \n");
+    assert_eq!(2, stopwords.len());
+    assert!(stopwords.contains("foo") && stopwords.contains("BAR"));
+    stopwords.insert("Baz");
+    assert!(stopwords.contains("baz"));
+    assert!(StopwordList::new().is_empty());
+
+    let path = std::env::temp_dir().join("text_score_test_stopwords.txt");
+    fs::write(&path, "foo\nbar\n").unwrap();
+    let loaded = StopwordList::from_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(StopwordList::from_words(&["foo", "bar"]), loaded);
+    assert!(StopwordList::from_file("/nonexistent/stopwords.txt").is_err());
+}
+#[test]
+fn test_stopword_filter(){
+    let tokenizer = StopwordList::from_words(&["the", "a"]).filter(WhitespaceTokenizer);
+    assert_eq!(vec!["cat", "sat"], tokenizer.tokenize("The cat a sat"));
+    assert!(tokenizer.tokenize("the a").is_empty());
+
+    // stopwords are looked up before stemming, "having" would not be found as "have"
+    let tokenizer = StemmingTokenizer::new(StopwordList::for_language(Language::English).filter(RougeTokenizer), PorterStemmer, 3);
+    assert_eq!(vec!["cat", "run"], tokenizer.tokenize("Having the cats running"));
+}
+#[test]
+fn test_rouge_without_stopwords(){
+    let score = rouge_n("the cat is on the mat", "a cat sat on a mat", 1).unwrap();
+    assert_eq!(0.5, score.recall);
+
+    let tokenizer = StopwordList::for_language(Language::English).filter(RougeTokenizer);
+    let score = rouge_n_with_tokenizer("the cat is on the mat", "a cat sat on a mat", 1, &tokenizer).unwrap();
+    assert_eq!(1.0, score.precision);
+    assert_eq!(2.0 / 3.0, score.recall);
+}
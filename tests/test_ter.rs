@@ -0,0 +1,76 @@
+use approx::assert_abs_diff_eq;
+use proptest::prelude::*;
+use text_score::ter::{corpus_ter, sentence_ter};
+
+#[test]
+fn test_sentence_ter(){
+    // test cases from sacrebleu test_ter.py
+    let score = sentence_ter("aaaa bbbb cccc dddd", &["aaaa bbbb cccc dddd"]).unwrap();
+    assert_eq!(0.0, score.score);
+    let score = sentence_ter("dddd eeee ffff", &["aaaa bbbb cccc"]).unwrap();
+    assert_eq!(1.0, score.score);
+    let score = sentence_ter("", &["a"]).unwrap();
+    assert_eq!(1.0, score.score);
+
+    // a single shift fixes the input
+    let score = sentence_ter("d e f g h a b c", &["a b c d e f g h"]).unwrap();
+    assert_abs_diff_eq!(1.0 / 8.0, score.score, epsilon = 1e-6);
+    assert_eq!((1, 1), (score.num_edits, score.num_shifts));
+
+    // example from Snover et al. (2006): 1 shift, 2 substitutions and 1 deletion
+    let score = sentence_ter(
+        "this week the saudis denied information published in the new york times",
+        &["saudi arabia denied this week information published in the american new york times"],
+    ).unwrap();
+    assert_eq!((4, 1), (score.num_edits, score.num_shifts));
+    assert_abs_diff_eq!(4.0 / 13.0, score.score, epsilon = 1e-6);
+
+    // a single word can be shifted as well
+    let score = sentence_ter("b a", &["a b"]).unwrap();
+    assert_eq!((1, 1), (score.num_edits, score.num_shifts));
+
+    // shifts are only done when they reduce the edits
+    let score = sentence_ter("a x b", &["a b"]).unwrap();
+    assert_eq!((1, 0), (score.num_edits, score.num_shifts));
+
+    // shifting a block right within its own span, at most to the end
+    let score = sentence_ter("d c b c b b", &["d c c b b b b"]).unwrap();
+    assert!(score.score.is_finite());
+
+    // case-sensitive
+    let score = sentence_ter("The cat", &["the cat"]).unwrap();
+    assert_eq!(1, score.num_edits);
+
+    assert!(sentence_ter("a", &[]).is_err());
+}
+#[test]
+fn test_sentence_ter_multi_reference(){
+    // fewest edits against any reference, divided by the average length
+    let score = sentence_ter("the cat sat", &["the cat sat on the mat", "a cat sat"]).unwrap();
+    assert_eq!(1, score.num_edits);
+    assert_abs_diff_eq!(4.5, score.reference_len, epsilon = 1e-6);
+    assert_abs_diff_eq!(1.0 / 4.5, score.score, epsilon = 1e-6);
+
+    // empty references
+    assert_eq!(1.0, sentence_ter("a b", &[""]).unwrap().score);
+    assert_eq!(0.0, sentence_ter("", &[""]).unwrap().score);
+}
+#[test]
+fn test_corpus_ter(){
+    let inputs = ["d e f g h a b c", "the cat sat"];
+    let references = [vec!["a b c d e f g h"], vec!["the cat sat on the mat", "a cat sat"]];
+
+    let score = corpus_ter(&inputs, &references).unwrap();
+    assert_eq!(2, score.num_edits);
+    assert_abs_diff_eq!(2.0 / 12.5, score.score, epsilon = 1e-6);
+
+    assert!(corpus_ter(&inputs, &references[..1]).is_err());
+}
+proptest! {
+    #[test]
+    fn test_ter_short_inputs(inputs in prop::collection::vec("([a-d] ?){0,8}", 1..3), reference in "([a-d] ?){0,8}") {
+        let references = vec![vec![reference.as_str()]; inputs.len()];
+        let score = corpus_ter(&inputs.iter().map(String::as_str).collect::<Vec<&str>>(), &references).unwrap();
+        prop_assert!(score.score.is_finite(), "{:?}", score);
+    }
+}
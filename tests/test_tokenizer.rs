@@ -0,0 +1,63 @@
+use text_score::tokenizer::{PunctuationTokenizer, RegexTokenizer, RougeTokenizer, Tokenizer, WhitespaceTokenizer};
+use text_score::rouge::{rouge_l_with_tokenizer, rouge_n, rouge_n_with_tokenizer, rouge_lsum_with_tokenizer};
+use text_score::bleu::{sentence_bleu, sentence_bleu_with_tokenizer, Smoothing, DEFAULT_MAX_ORDER};
+use text_score::edit_distance::wer_with_tokenizer;
+use text_score::meteor::MeteorScorer;
+
+#[test]
+fn test_whitespace_tokenizer(){
+    assert_eq!(vec!["a", "b.", "c"], WhitespaceTokenizer.tokenize(" a\tb.\n c "));
+    assert!(WhitespaceTokenizer.tokenize("  ").is_empty());
+}
+#[test]
+fn test_regex_tokenizer(){
+    let tokenizer = RegexTokenizer::new(r"\d+|[a-z]+").unwrap();
+    assert_eq!(vec!["abc", "123", "de"], tokenizer.tokenize("abc123 DE de!"));
+    // invalid pattern
+    assert!(RegexTokenizer::new("(").is_err());
+}
+#[test]
+fn test_punctuation_tokenizer(){
+    assert_eq!(vec!["Hello", ",", "world", "!?"], PunctuationTokenizer.tokenize("Hello, world!?"));
+    assert_eq!(vec!["naïve", "café", "."], PunctuationTokenizer.tokenize("naïve café."));
+    assert_eq!(vec!["snake_case", "(", "x", ")"], PunctuationTokenizer.tokenize("snake_case(x)"));
+}
+#[test]
+fn test_rouge_tokenizer(){
+    assert_eq!(vec!["the", "2", "cats", "sat"], RougeTokenizer.tokenize("The 2 CATS sat."));
+    // non-ascii letters are replaced by spaces, as rouge_score does
+    assert_eq!(vec!["caf", "au", "lait"], RougeTokenizer.tokenize("café au-lait"));
+}
+#[test]
+fn test_closure_tokenizer(){
+    let tokenizer = |text: &str| text.split(',').map(str::to_string).collect::<Vec<String>>();
+    assert_eq!(vec!["a b", "c"], tokenizer.tokenize("a b,c"));
+}
+#[test]
+fn test_rouge_with_tokenizer(){
+    // "case." and "case" only match once punctuation is removed
+    let score = rouge_n("this is a case.", "this is a case", 1).unwrap();
+    assert_eq!(0.75, score.f1);
+    let score = rouge_n_with_tokenizer("this is a case.", "This is a case", 1, &RougeTokenizer).unwrap();
+    assert_eq!(1.0, score.f1);
+
+    let score = rouge_l_with_tokenizer("the cat, sat", "the cat sat", &PunctuationTokenizer);
+    assert_eq!(0.75, score.precision);
+    assert_eq!(1.0, score.recall);
+
+    let score = rouge_lsum_with_tokenizer("The cat.\nIt sat.", "the cat\nit sat", &RougeTokenizer);
+    assert_eq!(1.0, score.f1);
+}
+#[test]
+fn test_other_metrics_with_tokenizer(){
+    let score = sentence_bleu("the cat is on the mat.", &["the cat is on the mat"], DEFAULT_MAX_ORDER, Smoothing::None).unwrap();
+    assert!(score.score < 1.0);
+    let score = sentence_bleu_with_tokenizer("the cat is on the mat.", &["the cat is on the mat"], DEFAULT_MAX_ORDER, Smoothing::None, &RougeTokenizer).unwrap();
+    assert_eq!(1.0, score.score);
+
+    let result = wer_with_tokenizer("Hello, world", "hello world", &RougeTokenizer);
+    assert_eq!(0.0, result.error_rate);
+
+    let scorer = MeteorScorer::new().with_tokenizer(RougeTokenizer);
+    assert_eq!(2, scorer.score("Hello, world!", "hello world").matches);
+}